@@ -45,6 +45,19 @@ struct Args {
     /// Location of the vnames configuration file
     #[clap(long, value_parser)]
     vnames_config: PathBuf,
+
+    /// Records the canonical paths of the crate's definitions, which the
+    /// indexer needs to generate signatures with --semantic_signatures
+    #[clap(long, action)]
+    def_paths: bool,
+
+    /// Path to the crate's rustdoc JSON, generated with
+    /// `rustdoc --output-format json`, to store in the kzip instead of running
+    /// the compiler to generate a save-analysis. The indexer only emits
+    /// definitions, documentation, and implementations from rustdoc JSON, as it
+    /// has no references.
+    #[clap(long, value_parser, conflicts_with = "def_paths")]
+    rustdoc_json: Option<PathBuf>,
 }
 
 fn main() -> Result<()> {
@@ -98,15 +111,19 @@ fn main() -> Result<()> {
         .ok_or_else(|| anyhow!("Failed to convert build output path file name to string"))?
         .to_string();
 
-    // Create temporary directory and run the analysis
+    // Create temporary directory and run the analysis, unless the crate's rustdoc
+    // JSON was provided
     let tmp_dir = TempDir::new("rust_extractor")
         .with_context(|| "Failed to make temporary directory".to_string())?;
     let build_target_arguments: Vec<String> = spawn_info.get_argument().to_vec();
-    save_analysis::generate_save_analysis(
-        build_target_arguments.clone(),
-        PathBuf::from(tmp_dir.path()),
-        &output_file_name,
-    )?;
+    if config.rustdoc_json.is_none() {
+        save_analysis::generate_save_analysis(
+            build_target_arguments.clone(),
+            PathBuf::from(tmp_dir.path()),
+            &output_file_name,
+            config.def_paths,
+        )?;
+    }
 
     // Create the output kzip
     let kzip_file = File::create(&config.output)
@@ -150,8 +167,14 @@ fn main() -> Result<()> {
         }
     }
 
-    // Add save analysis to kzip
-    let save_analysis_path = analysis_path_string(&output_file_name, tmp_dir.path())?;
+    // Add the save analysis, or the rustdoc JSON that replaces it, to kzip
+    let save_analysis_path = match &config.rustdoc_json {
+        Some(rustdoc_json) => rustdoc_json
+            .to_str()
+            .ok_or_else(|| anyhow!("rustdoc JSON file path is not valid UTF-8"))?
+            .to_string(),
+        None => analysis_path_string(&output_file_name, tmp_dir.path())?,
+    };
     let save_analysis_vname = create_vname(&mut vname_rules, &save_analysis_path, &default_corpus);
     kzip_add_required_input(
         &save_analysis_path,
@@ -160,6 +183,13 @@ fn main() -> Result<()> {
         &mut required_inputs,
     )?;
 
+    // Add the canonical paths of the save analysis definitions to kzip
+    if config.def_paths {
+        let def_paths_path = def_paths_path_string(&output_file_name, tmp_dir.path())?;
+        let def_paths_vname = create_vname(&mut vname_rules, &def_paths_path, &default_corpus);
+        kzip_add_required_input(&def_paths_path, def_paths_vname, &mut kzip, &mut required_inputs)?;
+    }
+
     // Create the IndexedCompilation and add it to the kzip
     let main_source_path = &rust_source_files[0];
     let mut unit_vname = create_vname(&mut vname_rules, main_source_path, &default_corpus);
@@ -327,6 +357,26 @@ fn analysis_path_string(output_file_name: &str, temp_dir_path: &Path) -> Result<
     }
 }
 
+/// Find the path of the file containing the canonical paths of the
+/// save_analysis definitions using the build target's output file name and the
+/// temporary base directory
+fn def_paths_path_string(output_file_name: &str, temp_dir_path: &Path) -> Result<String> {
+    // The path should always be {tmp_dir}/save-analysis/{output_file_name}.def_paths.json
+    let expected_path = temp_dir_path.join("save-analysis").join(format!(
+        "{}{}",
+        output_file_name,
+        kythe_rust_extractor::DEF_PATHS_SUFFIX
+    ));
+    if expected_path.exists() {
+        expected_path
+            .to_str()
+            .ok_or_else(|| anyhow!("Definition paths file path is not valid UTF-8"))
+            .map(String::from)
+    } else {
+        Err(anyhow!("Failed to find definition paths file in {:?}", temp_dir_path))
+    }
+}
+
 fn create_vname(rules: &mut [VNameRule], path: &str, default_corpus: &str) -> VName {
     for rule in rules {
         if rule.matches(path) {
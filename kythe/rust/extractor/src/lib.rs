@@ -14,27 +14,44 @@
 #![feature(rustc_private)]
 
 extern crate rustc_driver;
+extern crate rustc_hir;
 extern crate rustc_interface;
+extern crate rustc_middle;
 extern crate rustc_save_analysis;
 extern crate rustc_session;
+extern crate rustc_span;
 
 pub mod vname_util;
 
 use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::definitions::DefPathData;
 use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
 use rustc_save_analysis::DumpHandler;
-use std::path::PathBuf;
+use rustc_span::def_id::{CrateNum, DefId, DefIndex};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// The suffix of the file containing the canonical paths of the definitions
+/// in the save_analysis
+pub const DEF_PATHS_SUFFIX: &str = ".def_paths.json";
 
 /// Generate a save_analysis in `output_dir`
 ///
 /// `rustc_arguments` is a `Vec<String>` containing Rust compiler arguments. The
 /// first element must be an empty string.
 /// The save_analysis JSON output file will be located at
-/// {output_dir}/save-analysis/{crate_name}.json
+/// {output_dir}/save-analysis/{crate_name}.json. If `def_paths` is true, the
+/// canonical paths of its definitions, which the indexer uses to generate
+/// semantic signatures, are written to
+/// {output_dir}/save-analysis/{crate_name}.def_paths.json
 pub fn generate_analysis(
     rustc_arguments: Vec<String>,
     output_dir: PathBuf,
     output_file_name: &str,
+    def_paths: bool,
 ) -> Result<(), String> {
     let first_arg =
         rustc_arguments.get(0).ok_or_else(|| "Arguments vector should not be empty".to_string())?;
@@ -42,7 +59,7 @@ pub fn generate_analysis(
         return Err("The first argument must be an empty string".into());
     }
 
-    let mut callback_shim = CallbackShim::new(output_dir, output_file_name.to_string());
+    let mut callback_shim = CallbackShim::new(output_dir, output_file_name.to_string(), def_paths);
 
     rustc_driver::catch_fatal_errors(|| {
         RunCompiler::new(&rustc_arguments, &mut callback_shim).run()
@@ -50,7 +67,10 @@ pub fn generate_analysis(
     .map(|_| ())
     .map_err(|_| "A compiler error occurred".to_string())?;
 
-    Ok(())
+    match callback_shim.def_paths_error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
 }
 
 /// Handles compiler callbacks to enable and dump the save_analysis
@@ -58,12 +78,15 @@ pub fn generate_analysis(
 struct CallbackShim {
     output_dir: PathBuf,
     output_file_name: String,
+    def_paths: bool,
+    def_paths_error: Option<String>,
 }
 
 impl CallbackShim {
-    /// Create a new CallbackShim that dumps save_analysis files to `output_dir`
-    pub fn new(output_dir: PathBuf, output_file_name: String) -> Self {
-        Self { output_dir, output_file_name }
+    /// Create a new CallbackShim that dumps save_analysis files to `output_dir`,
+    /// along with the canonical paths of the definitions if `def_paths` is true
+    pub fn new(output_dir: PathBuf, output_file_name: String, def_paths: bool) -> Self {
+        Self { output_dir, output_file_name, def_paths, def_paths_error: None }
     }
 }
 
@@ -81,7 +104,7 @@ impl Callbacks for CallbackShim {
         let input = compiler.input();
         let crate_name = queries.crate_name().unwrap().peek().clone();
 
-        // Configure the save_analysis to include full documentation.
+        // Configure the save_analysis to include full documentation and signatures.
         // Normally this would be set using a `rls_data::config::Config` struct on the
         // fourth parameter of `process_crate`. However, the Rust compiler
         // falsely claims that there is a mismatch between rustc_save_analysis's
@@ -90,7 +113,7 @@ impl Callbacks for CallbackShim {
         // instead.
         std::env::set_var(
             "RUST_SAVE_ANALYSIS_CONFIG",
-            r#"{"output_file":null,"full_docs":true,"pub_only":false,"reachable_only":false,"distro_crate":false,"signatures":false,"borrow_data":false}"#,
+            r#"{"output_file":null,"full_docs":true,"pub_only":false,"reachable_only":false,"distro_crate":false,"signatures":true,"borrow_data":false}"#,
         );
 
         // Perform the save_analysis and dump it to the directory
@@ -102,9 +125,125 @@ impl Callbacks for CallbackShim {
                 input,
                 None,
                 DumpHandler::new(Some(self.output_dir.as_path()), &self.output_file_name),
-            )
+            );
+
+            // Record the canonical paths of the definitions so that the indexer can
+            // generate signatures that don't depend on their indices
+            if self.def_paths {
+                let analysis_dir = self.output_dir.join("save-analysis");
+                if let Err(error) = dump_def_paths(tcx, &analysis_dir, &self.output_file_name) {
+                    self.def_paths_error = Some(error);
+                }
+            }
         });
 
         Compilation::Stop
     }
 }
+
+/// The Id of a definition in the save_analysis
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct SaveAnalysisId {
+    krate: u32,
+    index: u32,
+}
+
+/// The canonical path of a definition
+#[derive(Serialize)]
+struct DefPathEntry {
+    id: SaveAnalysisId,
+    /// The namespace of the definition: "type", "value", or "macro"
+    namespace: &'static str,
+    /// The definition's path within its crate, such as `::shapes::Square` or
+    /// `::{impl#1}::area`
+    path: String,
+}
+
+/// Writes the canonical paths of the definitions and referenced items in the
+/// save_analysis named `output_file_name` in `analysis_dir`
+fn dump_def_paths(
+    tcx: TyCtxt<'_>,
+    analysis_dir: &Path,
+    output_file_name: &str,
+) -> Result<(), String> {
+    let analysis_path = analysis_dir.join(output_file_name).with_extension("json");
+    let analysis_contents = std::fs::read_to_string(&analysis_path)
+        .map_err(|e| format!("Failed to read save_analysis file: {}", e))?;
+    let analysis: Value = serde_json::from_str(&analysis_contents)
+        .map_err(|e| format!("Failed to parse save_analysis file: {}", e))?;
+
+    // Not every save_analysis Id is a DefId. Relations with inherent
+    // implementations use `u32::MAX` for the crate and index, and definitions
+    // without a DefId, such as some generic parameters, are given an index past
+    // the end of the crate's definitions.
+    let local_def_count = tcx.definitions_untracked().def_index_count();
+    let crate_nums: BTreeSet<u32> = tcx.crates(()).iter().map(|krate| krate.as_u32()).collect();
+    let entries: Vec<DefPathEntry> = get_def_ids(&analysis)
+        .into_iter()
+        .filter(|id| {
+            if id.krate == 0 {
+                (id.index as usize) < local_def_count
+            } else {
+                crate_nums.contains(&id.krate)
+            }
+        })
+        .map(|id| {
+            let def_id =
+                DefId { krate: CrateNum::from_u32(id.krate), index: DefIndex::from_u32(id.index) };
+            let namespace = match tcx.def_key(def_id).disambiguated_data.data {
+                DefPathData::CrateRoot | DefPathData::TypeNs(_) => "type",
+                DefPathData::MacroNs(_) => "macro",
+                _ => "value",
+            };
+            let path = tcx.def_path(def_id).to_string_no_crate_verbose();
+            DefPathEntry { id, namespace, path }
+        })
+        .collect();
+
+    let def_paths_path = analysis_dir.join(format!("{}{}", output_file_name, DEF_PATHS_SUFFIX));
+    let def_paths_contents = serde_json::to_string(&entries)
+        .map_err(|e| format!("Failed to serialize definition paths: {}", e))?;
+    std::fs::write(def_paths_path, def_paths_contents)
+        .map_err(|e| format!("Failed to write definition paths file: {}", e))
+}
+
+/// Returns the Ids of the crate's definitions and of the items in other crates
+/// that it references. Local variables are skipped because their Ids don't
+/// correspond to a definition in the compiler.
+fn get_def_ids(analysis: &Value) -> BTreeSet<SaveAnalysisId> {
+    let mut ids = BTreeSet::new();
+    for def in get_items(analysis, "defs") {
+        if def["kind"] != "Local" {
+            ids.extend(parse_id(&def["id"]));
+        }
+    }
+
+    let mut referenced_ids = Vec::new();
+    // The trait items implemented by items of trait implementations
+    for def in get_items(analysis, "defs") {
+        referenced_ids.extend(parse_id(&def["decl_id"]));
+    }
+    for reference in get_items(analysis, "refs").iter().chain(get_items(analysis, "imports")) {
+        referenced_ids.extend(parse_id(&reference["ref_id"]));
+    }
+    for relation in get_items(analysis, "relations") {
+        referenced_ids.extend(parse_id(&relation["from"]));
+        referenced_ids.extend(parse_id(&relation["to"]));
+    }
+    ids.extend(referenced_ids.into_iter().filter(|id| id.krate != 0));
+    ids
+}
+
+/// Returns the elements of the array `field` in the save_analysis
+fn get_items<'a>(analysis: &'a Value, field: &str) -> &'a [Value] {
+    analysis[field].as_array().map_or(&[], |items| items.as_slice())
+}
+
+/// Parses a save_analysis Id. Returns `None` if the value isn't an Id, such as
+/// the `ref_id` of a glob import.
+fn parse_id(id: &Value) -> Option<SaveAnalysisId> {
+    Some(SaveAnalysisId {
+        krate: u32::try_from(id["krate"].as_u64()?).ok()?,
+        index: u32::try_from(id["index"].as_u64()?).ok()?,
+    })
+}
@@ -97,6 +97,7 @@ fn main() -> Result<()> {
     missing_arguments_fail();
     bad_extra_action_path_fails();
     correct_arguments_succeed(extra_action_path_str, temp_dir_str, &output_key, arguments);
+    rustdoc_json_is_stored(extra_action_path_str, temp_dir_str);
 
     Ok(())
 }
@@ -155,6 +156,7 @@ fn correct_arguments_succeed(
         .arg(format!("--extra_action={}", extra_action_path_str))
         .arg(format!("--output={}", kzip_path_str))
         .arg(format!("--vnames_config={}", vnames_path.to_string_lossy()))
+        .arg("--def_paths")
         .status()
         .unwrap();
     assert_eq!(exit_status.code().unwrap(), 0);
@@ -221,7 +223,7 @@ fn correct_arguments_succeed(
     let required_inputs = compilation_unit.get_required_input().to_vec();
     assert_eq!(
         required_inputs.len(),
-        3,
+        4,
         "Unexpected number of required inputs: {}",
         required_inputs.len()
     );
@@ -264,4 +266,62 @@ fn correct_arguments_succeed(
         "Unexpected file path for save_analysis file: {}",
         analysis_path
     );
+
+    // Test attributes of the required_input for the definition paths
+    let def_paths_input = required_inputs.get(3).expect("Failed to get the fourth required input");
+    let def_paths_path = def_paths_input.get_info().get_path();
+    assert!(
+        def_paths_path.contains("save-analysis/libtest_crate-1234.def_paths.json"),
+        "Unexpected file path for definition paths file: {}",
+        def_paths_path
+    );
+}
+
+fn rustdoc_json_is_stored(extra_action_path_str: &str, temp_dir_str: &str) {
+    let r = Runfiles::create().unwrap();
+    let vnames_path = r.rlocation("io_kythe/external/io_kythe/kythe/data/vnames_config.json");
+
+    // The extractor only stores the rustdoc JSON, so its contents don't matter
+    let rustdoc_json_path_str = format!("{}/test_crate.json", temp_dir_str);
+    std::fs::write(&rustdoc_json_path_str, r#"{"root":"0:0","format_version":15}"#)
+        .expect("Failed to write rustdoc JSON file");
+
+    let extractor_path = std::env::var("EXTRACTOR_PATH").expect("Couldn't find extractor path");
+    let kzip_path_str = format!("{}/rustdoc.kzip", temp_dir_str);
+    let exit_status = Command::new(&extractor_path)
+        .arg(format!("--extra_action={}", extra_action_path_str))
+        .arg(format!("--output={}", kzip_path_str))
+        .arg(format!("--vnames_config={}", vnames_path.to_string_lossy()))
+        .arg(format!("--rustdoc_json={}", rustdoc_json_path_str))
+        .status()
+        .unwrap();
+    assert_eq!(exit_status.code().unwrap(), 0);
+
+    // Read the IndexedCompilation from the kzip
+    let kzip_file = File::open(&kzip_path_str).expect("Couldn't open resulting kzip file");
+    let mut kzip = zip::ZipArchive::new(kzip_file).expect("Couldn't read kzip archive");
+    let mut cu_path_str = String::from("");
+    for i in 0..kzip.len() {
+        let file = &kzip.by_index(i).expect("Couldn't get file in zip by index");
+        if file.is_file() && file.name().contains("pbunits/") {
+            cu_path_str = file.name().to_string();
+        }
+    }
+    assert_ne!(cu_path_str, "", "IndexedCompilation protobuf missing from kzip");
+    let cu_file = kzip.by_name(&cu_path_str).unwrap();
+    let mut cu_reader = BufReader::new(cu_file);
+    let indexed_compilation: IndexedCompilation =
+        protobuf::Message::parse_from_reader(&mut cu_reader)
+            .expect("Failed to parse protobuf as IndexedCompilation");
+
+    // The rustdoc JSON takes the place of the save analysis
+    let required_inputs = indexed_compilation.get_unit().get_required_input().to_vec();
+    assert_eq!(
+        required_inputs.len(),
+        3,
+        "Unexpected number of required inputs: {}",
+        required_inputs.len()
+    );
+    let rustdoc_input = required_inputs.get(2).expect("Failed to get the third required input");
+    assert_eq!(rustdoc_input.get_info().get_path(), rustdoc_json_path_str);
 }
@@ -12,7 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 extern crate kythe_rust_indexer;
-use kythe_rust_indexer::{indexer::KytheIndexer, providers::*, writer::CodedOutputStreamWriter};
+use kythe_rust_indexer::{
+    indexer::{
+        sysroot::{SysrootCorpus, DEFAULT_SYSROOT_CORPUS},
+        KytheIndexer,
+    },
+    providers::*,
+    writer::CodedOutputStreamWriter,
+};
 
 use anyhow::{Context, Result};
 use clap::Parser;
@@ -35,11 +42,35 @@ struct Args {
     /// Emits built-in types in the "std" corpus
     #[clap(long, action)]
     tbuiltin_std_corpus: bool,
+
+    /// Generates definition signatures from canonical paths recorded by the
+    /// extractor with --def_paths instead of save-analysis indices
+    #[clap(long, action)]
+    semantic_signatures: bool,
+
+    /// The corpus that the sources of the sysroot crates, such as std and core,
+    /// are indexed in. When set, references to the sysroot crates target this
+    /// corpus instead of the CompilationUnit's corpus. Defaults to "rust-src"
+    /// if the flag is passed without a value, like the sysroot indexer.
+    #[clap(
+        long,
+        value_parser,
+        min_values = 0,
+        require_equals = true,
+        default_missing_value = DEFAULT_SYSROOT_CORPUS
+    )]
+    sysroot_corpus: Option<String>,
+
+    /// The root that the sources of the sysroot crates are indexed in
+    #[clap(long, value_parser, default_value = "")]
+    sysroot_root: String,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     let emit_std_lib = !args.no_emit_std_lib;
+    let sysroot_corpus =
+        args.sysroot_corpus.map(|corpus| SysrootCorpus { corpus, root: args.sysroot_root });
 
     // Get kzip path from argument and use it to create a KzipFileProvider
     // Unwrap is safe because the parameter is required
@@ -56,7 +87,14 @@ fn main() -> Result<()> {
     let mut indexer = KytheIndexer::new(&mut writer);
 
     for unit in compilation_units {
-        indexer.index_cu(&unit, &mut kzip_provider, emit_std_lib, args.tbuiltin_std_corpus)?;
+        indexer.index_cu(
+            &unit,
+            &mut kzip_provider,
+            emit_std_lib,
+            args.tbuiltin_std_corpus,
+            args.semantic_signatures,
+            sysroot_corpus.as_ref(),
+        )?;
     }
     Ok(())
 }
@@ -12,7 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 extern crate kythe_rust_indexer;
-use kythe_rust_indexer::{indexer::KytheIndexer, providers::*, proxyrequests, writer::ProxyWriter};
+use kythe_rust_indexer::{
+    indexer::{
+        sysroot::{SysrootCorpus, DEFAULT_SYSROOT_CORPUS},
+        KytheIndexer,
+    },
+    providers::*,
+    proxyrequests,
+    writer::ProxyWriter,
+};
 
 use analysis_rust_proto::*;
 use anyhow::{anyhow, Context, Result};
@@ -32,6 +40,28 @@ struct Args {
     /// Emits built-in types in the "std" corpus
     #[clap(long, action)]
     tbuiltin_std_corpus: bool,
+
+    /// Generates definition signatures from canonical paths recorded by the
+    /// extractor with --def_paths instead of save-analysis indices
+    #[clap(long, action)]
+    semantic_signatures: bool,
+
+    /// The corpus that the sources of the sysroot crates, such as std and core,
+    /// are indexed in. When set, references to the sysroot crates target this
+    /// corpus instead of the CompilationUnit's corpus. Defaults to "rust-src"
+    /// if the flag is passed without a value, like the sysroot indexer.
+    #[clap(
+        long,
+        value_parser,
+        min_values = 0,
+        require_equals = true,
+        default_missing_value = DEFAULT_SYSROOT_CORPUS
+    )]
+    sysroot_corpus: Option<String>,
+
+    /// The root that the sources of the sysroot crates are indexed in
+    #[clap(long, value_parser, default_value = "")]
+    sysroot_root: String,
 }
 
 fn main() -> Result<()> {
@@ -41,12 +71,21 @@ fn main() -> Result<()> {
 
     let args = Args::parse();
     let emit_std_lib = !args.no_emit_std_lib;
+    let sysroot_corpus =
+        args.sysroot_corpus.map(|corpus| SysrootCorpus { corpus, root: args.sysroot_root });
 
     // Request and process
     loop {
         let unit = request_compilation_unit()?;
         // Index the CompilationUnit and let the proxy know we are done
-        match indexer.index_cu(&unit, &mut file_provider, emit_std_lib, args.tbuiltin_std_corpus) {
+        match indexer.index_cu(
+            &unit,
+            &mut file_provider,
+            emit_std_lib,
+            args.tbuiltin_std_corpus,
+            args.semantic_signatures,
+            sysroot_corpus.as_ref(),
+        ) {
             Ok(_) => send_done(true, String::new())?,
             Err(e) => send_done(false, e.to_string())?,
         }
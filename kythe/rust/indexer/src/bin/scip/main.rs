@@ -0,0 +1,74 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate kythe_rust_indexer;
+use kythe_rust_indexer::{
+    indexer::scip::{get_project_root, ScipConverter},
+    providers::LocalFileProvider,
+    writer::CodedOutputStreamWriter,
+};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use scip_rust_proto::Index;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[clap(author = "The Kythe Authors")]
+#[clap(about = "Kythe Rust SCIP Converter", long_about = None)]
+#[clap(rename_all = "snake_case")]
+struct Args {
+    /// The path to the SCIP index to be converted, such as the index.scip
+    /// written by `rust-analyzer scip`
+    #[clap(value_parser)]
+    index_path: PathBuf,
+
+    /// The directory that the paths of the index's documents are relative to.
+    /// Defaults to the project root recorded in the index
+    #[clap(long, value_parser)]
+    source_root: Option<PathBuf>,
+
+    /// The corpus to place the files and symbols of the index in
+    #[clap(long, value_parser, default_value = "")]
+    corpus: String,
+
+    /// The root to place the files and symbols of the index in
+    #[clap(long, value_parser, default_value = "")]
+    root: String,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let index_bytes = std::fs::read(&args.index_path).context("Failed to read the SCIP index")?;
+    let index: Index = protobuf::Message::parse_from_bytes(&index_bytes)
+        .context("Failed to parse the SCIP index")?;
+    let source_root = match args.source_root {
+        Some(source_root) => source_root,
+        None => get_project_root(&index)
+            .context("The index's project root isn't a local directory. Use --source_root")?,
+    };
+
+    // Register the source files of the documents that don't store their text
+    let mut provider = LocalFileProvider::new();
+    for document in index.get_documents() {
+        let path = document.get_relative_path();
+        provider.add_file(path, &source_root.join(path));
+    }
+
+    let mut stdout_writer = std::io::stdout();
+    let mut writer = CodedOutputStreamWriter::new(&mut stdout_writer);
+    let mut converter = ScipConverter::new(&mut writer, &args.corpus, &args.root);
+    converter.convert(&index, &mut provider).context("Failed to convert the SCIP index")?;
+    Ok(())
+}
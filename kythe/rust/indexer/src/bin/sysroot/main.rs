@@ -0,0 +1,90 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate kythe_rust_indexer;
+use kythe_rust_indexer::{
+    indexer::{sysroot::*, KytheIndexer},
+    providers::*,
+    writer::CodedOutputStreamWriter,
+};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[clap(author = "The Kythe Authors")]
+#[clap(about = "Kythe Rust Sysroot Indexer")]
+#[clap(long_about = "Kythe Rust Sysroot Indexer
+
+Indexes the standard library crates of a toolchain from their save-analysis \
+files. The save-analysis was removed from rustc in Rust 1.68, so only \
+toolchains older than 1.68 that ship the rust-analysis component are \
+supported, such as the nightly-2022-07-27 toolchain used to build Kythe.")]
+#[clap(rename_all = "snake_case")]
+struct Args {
+    /// The path to the toolchain's sysroot, which must contain the rust-src and
+    /// rust-analysis components
+    #[clap(value_parser)]
+    sysroot: PathBuf,
+
+    /// The corpus to index the sources of the sysroot crates in
+    #[clap(long, value_parser, default_value = DEFAULT_SYSROOT_CORPUS)]
+    sysroot_corpus: String,
+
+    /// The root to index the sources of the sysroot crates in
+    #[clap(long, value_parser, default_value = "")]
+    sysroot_root: String,
+
+    /// Emits built-in types in the "std" corpus
+    #[clap(long, action)]
+    tbuiltin_std_corpus: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let sysroot_corpus = SysrootCorpus { corpus: args.sysroot_corpus, root: args.sysroot_root };
+
+    let analysis_files = find_sysroot_analysis_files(&args.sysroot)
+        .context("Failed to find the save-analysis files in the sysroot")?;
+    if analysis_files.is_empty() {
+        return Err(anyhow!(
+            "No save-analysis files were found for the sysroot crates. Is the rust-analysis component installed?"
+        ));
+    }
+
+    // Create instances of StreamWriter and KytheIndexer
+    let mut stdout_writer = std::io::stdout();
+    let mut writer = CodedOutputStreamWriter::new(&mut stdout_writer);
+    let mut indexer = KytheIndexer::new(&mut writer);
+
+    for (krate_name, analysis_path) in analysis_files {
+        let mut provider = LocalFileProvider::new();
+        let unit =
+            create_sysroot_unit(&args.sysroot, &analysis_path, &sysroot_corpus, &mut provider)
+                .with_context(|| {
+                    format!("Failed to create a CompilationUnit for {}", krate_name)
+                })?;
+        indexer
+            .index_cu(
+                &unit,
+                &mut provider,
+                true,
+                args.tbuiltin_std_corpus,
+                false,
+                Some(&sysroot_corpus),
+            )
+            .with_context(|| format!("Failed to index {}", krate_name))?;
+    }
+    Ok(())
+}
@@ -16,14 +16,29 @@ use crate::error::KytheError;
 use crate::providers::FileProvider;
 use crate::writer::KytheWriter;
 
+use super::docs::{find_doc_links, generate_doc_markup, DocLink};
 use super::entries::EntryEmitter;
+use super::marked_source::generate_marked_source;
+use super::model::{
+    CrateAnalysis, CrateId, DefId, DefKind, Definition, Implementation, Import, ImportKind,
+    RefKind, RelationKind, Span,
+};
 use super::offset::OffsetIndex;
+use super::scanner::{
+    find_call_span, find_enum_variants, find_generic_param_uses, find_generic_params,
+    find_impl_keyword, find_item_span, find_item_visibility, find_macro_definitions,
+    find_macro_name, find_module_declarations, find_outer_attributes, find_parameters,
+    find_type_alias_value, is_uninitialized_local, parse_attribute, parse_integer_literal,
+    parse_type, GenericParam, GenericParamKind, ModuleDeclaration, TypeSyntax,
+};
+use super::sysroot::{get_library_path, is_sysroot_crate, SysrootCorpus};
 
 use analysis_rust_proto::CompilationUnit;
 use path_clean::clean;
-use rls_data::{Analysis, Def, DefKind};
-use std::collections::HashMap;
+use protobuf::Message;
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
+use std::path::Path;
 use storage_rust_proto::*;
 
 /// A data structure to analyze and index CompilationUnit protobufs
@@ -52,24 +67,57 @@ pub struct CrateAnalyzer<'a, 'b> {
     file_vnames: &'b HashMap<String, VName>,
     // The current CompilationUnit's VName
     unit_vname: &'b VName,
-    // The save-analysis for the crate
-    analysis: Analysis,
-    // A map between a crate's identifier and a string consisting of
-    // "<disambiguator1>_<disambiguator2>"
-    krate_ids: HashMap<u32, rls_data::GlobalCrateId>,
+    // The analysis of the crate
+    analysis: CrateAnalysis,
+    // A map between a crate's number and its identifier
+    krate_ids: HashMap<u32, CrateId>,
     // The crate's VName
     krate_vname: VName,
     // Stores the parent of a child definition so that a childof edge can be emitted when the
     // child's definition is analyzed
-    children_ids: HashMap<rls_data::Id, VName>,
+    children_ids: HashMap<DefId, VName>,
     // Stores VNames for emitted definitions based on definition Id
-    definition_vnames: HashMap<rls_data::Id, VName>,
+    definition_vnames: HashMap<DefId, VName>,
     // An index for computing byte offsets in files based on line and column number
     offset_index: &'b OffsetIndex,
     // An index for mapping a method's definition Id to what it is implementing
-    method_index: HashMap<rls_data::Id, MethodImpl>,
+    method_index: HashMap<DefId, MethodImpl>,
     // A map from type names to their vnames
     type_vnames: HashMap<String, VName>,
+    // A map from the qualified names of the crate's structs, enums, and unions
+    // to their definition Ids
+    type_ids: HashMap<String, DefId>,
+    // A map between a function's definition Id and the definition Ids of the
+    // variables declared by each of its parameters, indexed by position
+    function_params: HashMap<DefId, Vec<Vec<DefId>>>,
+    // A map between the file name and starting byte of a generic type
+    // parameter's name and its definition Id
+    generic_params: HashMap<(String, u32), DefId>,
+    // The definition Ids of the crate's generic type parameters
+    generic_param_ids: HashSet<DefId>,
+    // A map between the definition Id of a foreign item and the definition Id of
+    // the module containing its `extern` block
+    foreign_item_modules: HashMap<DefId, DefId>,
+    // A map between a file name and the spans and VNames of the functions and
+    // methods defined in it
+    function_spans: HashMap<String, Vec<(ByteSpan, VName)>>,
+    // A map between the file name and starting byte of an enum variant's name
+    // and the value of its discriminant
+    discriminants: HashMap<(String, u32), String>,
+    // A map between the file name and starting byte of a reference to a type and
+    // the definition Id of the type
+    type_refs: HashMap<(String, u32), DefId>,
+    // The signatures of the tapp nodes that have been emitted
+    tapp_signatures: HashSet<String>,
+    // The definition Ids of the enum variant and trait items, and their
+    // children, which don't have a visibility of their own
+    inherited_visibility_ids: HashSet<DefId>,
+    // A map between the path of a definition in the crate, such as
+    // `module::Type::method`, and its definition Id
+    def_paths: HashMap<String, DefId>,
+    // The corpus and root that the sysroot crates are indexed in, if their
+    // nodes shouldn't be placed in the CompilationUnit's corpus
+    sysroot_corpus: Option<SysrootCorpus>,
     // Whether do emit references to the standard library
     emit_std_lib: bool,
 }
@@ -78,9 +126,11 @@ pub struct CrateAnalyzer<'a, 'b> {
 /// map a method definition Id to its struct and corresponding trait.
 pub struct MethodImpl {
     // The struct definition Id the method is being implemented on
-    pub struct_target: rls_data::Id,
+    pub struct_target: DefId,
     // The trait definition Id, if any, this is being implemented for
-    pub trait_target: Option<rls_data::Id>,
+    pub trait_target: Option<DefId>,
+    // The definition Id of the trait method, if any, this is overriding
+    pub trait_method: Option<DefId>,
 }
 
 /// Represents a span within a file based on byte offets
@@ -177,11 +227,16 @@ impl<'a> UnitAnalyzer<'a> {
     }
 
     /// Indexes the provided crate
+    ///
+    /// When a definition has a canonical path in the analysis, its signature is
+    /// generated from the path instead of its index. The nodes of the sysroot
+    /// crates are placed in `sysroot_corpus` if it is provided.
     pub fn index_crate(
         &mut self,
-        analysis: Analysis,
+        analysis: CrateAnalysis,
         emit_std_lib: bool,
         tbuiltin_std_corpus: bool,
+        sysroot_corpus: Option<SysrootCorpus>,
     ) -> Result<(), KytheError> {
         let mut crate_analyzer = CrateAnalyzer::new(
             &mut self.emitter,
@@ -191,13 +246,18 @@ impl<'a> UnitAnalyzer<'a> {
             &self.offset_index,
             emit_std_lib,
             tbuiltin_std_corpus,
+            sysroot_corpus,
         );
         crate_analyzer.emit_crate_nodes()?;
         crate_analyzer.emit_tbuiltin_nodes()?;
         crate_analyzer.process_implementations()?;
         crate_analyzer.emit_definitions()?;
+        crate_analyzer.emit_implementations()?;
+        crate_analyzer.emit_relations()?;
+        crate_analyzer.emit_macros()?;
         crate_analyzer.emit_import_xrefs()?;
         crate_analyzer.emit_xrefs()?;
+        crate_analyzer.emit_module_declarations()?;
         Ok(())
     }
 }
@@ -205,14 +265,16 @@ impl<'a> UnitAnalyzer<'a> {
 impl<'a, 'b> CrateAnalyzer<'a, 'b> {
     /// Create a new instance to analyze `krate` which will emit to `emitter`
     /// and use `file_vnames` to resolve VNames from file names
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         emitter: &'b mut EntryEmitter<'a>,
         file_vnames: &'b HashMap<String, VName>,
         unit_vname: &'b VName,
-        analysis: Analysis,
+        analysis: CrateAnalysis,
         offset_index: &'b OffsetIndex,
         emit_std_lib: bool,
         tbuiltin_std_corpus: bool,
+        sysroot_corpus: Option<SysrootCorpus>,
     ) -> Self {
         // Initialize the type_vnames HashMap with builtin types
         let types = vec![
@@ -273,16 +335,35 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             offset_index,
             method_index: HashMap::new(),
             type_vnames,
+            type_ids: HashMap::new(),
+            function_params: HashMap::new(),
+            generic_params: HashMap::new(),
+            generic_param_ids: HashSet::new(),
+            foreign_item_modules: HashMap::new(),
+            function_spans: HashMap::new(),
+            discriminants: HashMap::new(),
+            type_refs: HashMap::new(),
+            tapp_signatures: HashSet::new(),
+            inherited_visibility_ids: HashSet::new(),
+            def_paths: HashMap::new(),
+            sysroot_corpus,
             emit_std_lib,
         }
     }
 
     /// Given a signature, generates a VName for a crate based on the VName of
     /// the CompilationUnit
-    fn generate_crate_vname(&self, krate_id: &rls_analysis::CrateId) -> VName {
+    fn generate_crate_vname(&self, krate_id: &CrateId) -> VName {
         let signature =
             format!("{}_{}_{}", krate_id.disambiguator.0, krate_id.disambiguator.1, krate_id.name);
         let mut krate_vname = self.unit_vname.clone();
+        // The sysroot crates are placed in the corpus that their sources are indexed in
+        if let Some(sysroot_corpus) = &self.sysroot_corpus {
+            if is_sysroot_crate(&krate_id.name) {
+                krate_vname.set_corpus(sysroot_corpus.corpus.clone());
+                krate_vname.set_root(sysroot_corpus.root.clone());
+            }
+        }
         krate_vname.set_signature(signature);
         krate_vname.set_language("rust".to_owned());
         krate_vname.clear_path();
@@ -292,23 +373,18 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
     /// Generates and emits package nodes for the main crate and external crates
     /// NOTE: Must be called first to populate the self.krate_ids HashMap
     pub fn emit_crate_nodes(&mut self) -> Result<(), KytheError> {
-        let krate_prelude = &self.analysis.prelude.as_ref().ok_or_else(|| {
-            KytheError::IndexerError("Crate did not have prelude data".to_string())
-        })?;
-
         assert!(self.krate_ids.is_empty());
-        self.krate_ids.reserve(1 + krate_prelude.external_crates.len());
+        self.krate_ids.reserve(1 + self.analysis.external_crates.len());
 
         // First emit the node for our own crate and add it to the HashMap
-        let krate_id = &krate_prelude.crate_id;
+        let krate_id = &self.analysis.krate;
         let krate_vname = self.generate_crate_vname(krate_id);
         self.krate_vname = krate_vname.clone();
         self.emitter.emit_fact(&krate_vname, "/kythe/node/kind", b"package".to_vec())?;
         self.krate_ids.insert(0u32, krate_id.clone());
 
         // Then, do the same for all of the external crates
-        for (krate_num, external_krate) in krate_prelude.external_crates.iter().enumerate() {
-            let krate_id = &external_krate.id;
+        for (krate_num, krate_id) in self.analysis.external_crates.iter().enumerate() {
             let krate_vname = self.generate_crate_vname(krate_id);
             self.emitter.emit_fact(&krate_vname, "/kythe/node/kind", b"package".to_vec())?;
             self.krate_ids.insert((krate_num + 1) as u32, krate_id.clone());
@@ -327,43 +403,64 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
     }
 
     /// Creates the internal `method_index` by analyzing the implementations and
-    /// relations on the save_analysis.
+    /// relations in the analysis.
     ///
     /// Must be called before "emit_definitions"
     pub fn process_implementations(&mut self) -> Result<(), KytheError> {
         // Create a HashMap mapping the implementation Id to the implementation
         // It might be a safe assumption that the index is the Id, but we can't be too
         // careful
-        let impls = &self.analysis.impls;
-        let mut impl_map: HashMap<u32, rls_data::Impl> = HashMap::with_capacity(impls.len());
+        let impls = &self.analysis.implementations;
+        let mut impl_map: HashMap<u32, Implementation> = HashMap::with_capacity(impls.len());
         for implementation in impls.iter() {
             impl_map.insert(implementation.id, implementation.clone());
         }
 
+        // Create a HashMap between a definition Id and its definition so that trait
+        // implementation methods can be matched to the trait methods they override
+        let defs: HashMap<DefId, &Definition> =
+            self.analysis.definitions.iter().map(|def| (def.id, def)).collect();
+
         // Create a HashMap betwen a method definition Id and the struct and trait being
         // implemented on
-        let mut method_index: HashMap<rls_data::Id, MethodImpl> = HashMap::new();
+        let mut method_index: HashMap<DefId, MethodImpl> = HashMap::new();
         let relations = &self.analysis.relations;
         for relation in relations.iter() {
             // If this is an implementation relation
-            if let rls_data::RelationKind::Impl { id: impl_id, .. } = relation.kind {
+            if let RelationKind::Impl { implementation: impl_id } = relation.kind {
                 let implementation = impl_map.get(&impl_id).ok_or_else(|| {
                     KytheError::IndexerError(format!(
                         "Couldn't find implementation for relation {:?}",
                         relation
                     ))
                 })?;
+                // The struct being implemented on
+                let struct_target = match relation.from {
+                    Some(struct_target) => struct_target,
+                    None => continue,
+                };
                 // Add all of the childred to the HashMap
                 for child in implementation.children.iter() {
-                    // The struct being implemented on
-                    let struct_target = relation.from;
                     // The optional trait being implemented on
-                    // The save_analysis file will have the krate and index be maxint if the
-                    // implementation is not on a trait.
-                    const MAX_INT: u32 = 4294967295;
-                    let trait_target =
-                        if relation.to.krate != MAX_INT { Some(relation.to) } else { None };
-                    method_index.insert(*child, MethodImpl { struct_target, trait_target });
+                    let trait_target = relation.to;
+                    // The trait method being implemented, which the analysis may provide
+                    // even if the trait is defined in another crate. Otherwise, the trait
+                    // method with the same name is found if the trait is defined in this crate.
+                    let child_def = defs.get(child);
+                    let trait_method = child_def.and_then(|def| def.declaration).or_else(|| {
+                        let method_name = &child_def?.name;
+                        defs.get(&trait_target?)?.children.iter().copied().find(|trait_child| {
+                            match defs.get(trait_child) {
+                                Some(trait_child_def) => {
+                                    trait_child_def.kind == DefKind::Method
+                                        && &trait_child_def.name == method_name
+                                }
+                                None => false,
+                            }
+                        })
+                    });
+                    method_index
+                        .insert(*child, MethodImpl { struct_target, trait_target, trait_method });
                 }
             }
         }
@@ -371,6 +468,52 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
         Ok(())
     }
 
+    /// Emits `satisfies` edges from types to the traits they implement and
+    /// `extends` edges from traits to their supertraits
+    pub fn emit_relations(&mut self) -> Result<(), KytheError> {
+        assert!(!self.krate_ids.is_empty());
+
+        // A type may implement the same trait multiple times with different generic
+        // arguments, so we track the emitted edges to avoid duplicates
+        let mut emitted: HashSet<(DefId, DefId)> = HashSet::new();
+
+        // We must clone to avoid double borrowing "self"
+        let relations = self.analysis.relations.clone();
+        for relation in relations.iter() {
+            // Either side of the relation may not be a definition, such as for inherent
+            // implementations
+            let (from, to) = match (relation.from, relation.to) {
+                (Some(from), Some(to)) => (from, to),
+                _ => continue,
+            };
+            let (source_id, target_id, edge_kind) = match relation.kind {
+                RelationKind::Impl { .. } => (from, to, "/kythe/edge/satisfies"),
+                // For supertrait relations, `from` is the supertrait and `to` is the trait
+                // that extends it
+                RelationKind::SuperTrait => (to, from, "/kythe/edge/extends"),
+            };
+            if !emitted.insert((source_id, target_id)) {
+                continue;
+            }
+
+            // Skip relations involving the standard library unless requested
+            let involves_std = [source_id, target_id]
+                .iter()
+                .filter_map(|id| self.krate_ids.get(&id.krate))
+                .any(|krate_id| is_sysroot_crate(&krate_id.name));
+            if involves_std && !self.emit_std_lib {
+                continue;
+            }
+
+            if let (Some(source_vname), Some(target_vname)) =
+                (self.get_def_vname(&source_id), self.get_def_vname(&target_id))
+            {
+                self.emitter.emit_edge(&source_vname, &target_vname, edge_kind)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Given a definition for a module, returns the byte span
     /// for the module definition
     ///
@@ -378,7 +521,7 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
     /// If the definition provided isn't for a module, `false` is returned.
     /// If the span file name is called `mod.rs` but there is no parent
     /// directory, `false` is returned.
-    fn is_module_implicit(&self, def: &Def) -> bool {
+    fn is_module_implicit(&self, def: &Definition) -> bool {
         // Ensure that this defition is for a module
         if def.kind != DefKind::Mod {
             return false;
@@ -390,7 +533,7 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             return true;
         }
 
-        let file_path = def.span.file_name.clone();
+        let file_path = Path::new(&def.span.file_name);
 
         // The name we expect if the module definition is the file itself
         let expected_name: String;
@@ -413,24 +556,101 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
         def.name == expected_name
     }
 
+    /// Emits nodes for generic implementations, such as `impl<T> Wrapper<T>`,
+    /// with `tparam.N` edges to their generic parameters and a `defines`
+    /// anchor over the implementation. Other implementations don't declare
+    /// anything that needs a node, as their items are children of the type
+    /// being implemented on.
+    pub fn emit_implementations(&mut self) -> Result<(), KytheError> {
+        let implementations = self.analysis.implementations.clone();
+        for implementation in implementations.iter() {
+            let span = match &implementation.span {
+                Some(span) => span,
+                None => continue,
+            };
+            let file_name = clean(&span.file_name);
+            let (file_contents, file_vname) = match (
+                self.offset_index.get_file_contents(&file_name),
+                self.file_vnames.get(&file_name),
+            ) {
+                (Some(file_contents), Some(file_vname)) => (file_contents, file_vname),
+                _ => continue,
+            };
+            let keyword_span = self
+                .offset_index
+                .get_byte_offset(&file_name, span.line_start, span.column_start)
+                .and_then(|self_type_start| find_impl_keyword(file_contents, self_type_start));
+            let keyword_span = match keyword_span {
+                Some(keyword_span) => keyword_span,
+                None => continue,
+            };
+            let params = find_generic_params(file_contents, keyword_span.end_byte);
+            if params.iter().all(|param| param.kind == GenericParamKind::Lifetime) {
+                continue;
+            }
+
+            let mut impl_vname = self.krate_vname.clone();
+            impl_vname.set_signature(format!(
+                "{}_impl_{}",
+                self.krate_vname.get_signature(),
+                implementation.id
+            ));
+            self.emitter.emit_fact(&impl_vname, "/kythe/node/kind", b"record".to_vec())?;
+            self.emitter.emit_fact(&impl_vname, "/kythe/subkind", b"impl".to_vec())?;
+            self.emitter.emit_fact(&impl_vname, "/kythe/complete", b"definition".to_vec())?;
+            self.emit_generic_param_edges(&impl_vname, &file_name, &params, file_vname)?;
+
+            if let Some(item_span) = find_item_span(file_contents, &keyword_span) {
+                let mut anchor_vname = impl_vname.clone();
+                anchor_vname
+                    .set_signature(format!("{}_defines_anchor", impl_vname.get_signature()));
+                anchor_vname.set_root(file_vname.get_root().to_string());
+                anchor_vname.set_path(file_vname.get_path().to_string());
+                self.emitter.emit_anchor_edge(
+                    &anchor_vname,
+                    &impl_vname,
+                    item_span,
+                    "/kythe/edge/defines",
+                )?;
+            }
+        }
+        Ok(())
+    }
+
     /// Emit Kythe graph information for the definitions in the crate
     pub fn emit_definitions(&mut self) -> Result<(), KytheError> {
         assert!(!self.krate_ids.is_empty());
 
         // We must clone to avoid double borrowing "self"
-        let defs = self.analysis.defs.clone();
+        let defs = self.analysis.definitions.clone();
+
+        // Index the crate's types by qualified name so that `typed` edges can be
+        // emitted for definitions whose type is declared later in the crate
+        for def in defs.iter() {
+            if matches!(def.kind, DefKind::Enum | DefKind::Struct | DefKind::Union) {
+                self.type_ids.insert(def.qualname.trim_start_matches("::").to_string(), def.id);
+            }
+        }
+
+        self.index_parameters(&defs)?;
+        self.index_generic_params(&defs)?;
+        self.index_foreign_items(&defs);
+        self.index_discriminants(&defs)?;
+        self.index_type_refs()?;
+        self.index_def_paths(&defs);
+        self.index_inherited_visibility(&defs);
 
         for def in &defs {
-            let file_name = clean(def.span.file_name.to_str().unwrap());
+            let file_name = clean(&def.span.file_name);
             let file_vname = match self.file_vnames.get(&file_name) {
                 Some(v) => v,
-                // save_analysis sometimes references files that we don't have as file nodes
+                // The analysis sometimes references files that we don't have as file nodes
                 _ => continue,
             };
 
             if let Some(krate_id) = self.krate_ids.get(&def.id.krate) {
                 // Generate node based on definition type
-                let def_vname = self.generate_def_vname(krate_id, def.id.index);
+                let def_vname = self.generate_def_vname(krate_id, &def.id);
                 self.emit_definition_node(&def_vname, def, file_vname)?;
             } else {
                 // Generate a diagnostic node indicating that we couldn't find the refernced
@@ -465,12 +685,429 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
         Ok(())
     }
 
+    /// Creates the internal `function_params` index by finding the local
+    /// variables that are declared inside of each function's parameter list.
+    ///
+    /// The analysis emits parameters as regular `Local` definitions, so
+    /// the parameters are located in the source text following the
+    /// function's name.
+    fn index_parameters(&mut self, defs: &[Definition]) -> Result<(), KytheError> {
+        // A map between a file name and the parameter spans of the functions
+        // defined in it
+        let mut param_lists: HashMap<String, Vec<(Vec<ByteSpan>, DefId)>> = HashMap::new();
+        for def in defs.iter() {
+            if def.kind != DefKind::Function && def.kind != DefKind::Method {
+                continue;
+            }
+            let file_name = clean(&def.span.file_name);
+            let name_span = match self.get_byte_span(&def.id, &def.span)? {
+                Some(span) => span,
+                None => continue,
+            };
+            let file_contents = match self.offset_index.get_file_contents(&file_name) {
+                Some(contents) => contents,
+                None => continue,
+            };
+            if let Some(params) = find_parameters(file_contents, name_span.end_byte) {
+                param_lists.entry(file_name).or_default().push((params, def.id));
+            }
+        }
+
+        // Assign each local variable declared inside of a parameter to the
+        // function and the parameter's position. A pattern such as `(a, b)`
+        // declares several variables in the same position.
+        let mut function_params: HashMap<DefId, Vec<Vec<DefId>>> = HashMap::new();
+        for def in defs.iter() {
+            if def.kind != DefKind::Local {
+                continue;
+            }
+            let file_name = clean(&def.span.file_name);
+            let functions = match param_lists.get(&file_name) {
+                Some(functions) => functions,
+                None => continue,
+            };
+            let start_byte = match self.get_byte_span(&def.id, &def.span)? {
+                Some(span) => span.start_byte,
+                None => continue,
+            };
+            let param = functions.iter().find_map(|(params, function_id)| {
+                params
+                    .iter()
+                    .position(|span| span.start_byte <= start_byte && start_byte < span.end_byte)
+                    .map(|position| (function_id, position, params.len()))
+            });
+            if let Some((function_id, position, param_count)) = param {
+                let params = function_params
+                    .entry(*function_id)
+                    .or_insert_with(|| vec![Vec::new(); param_count]);
+                params[position].push(def.id);
+            }
+        }
+        self.function_params = function_params;
+        Ok(())
+    }
+
+    /// Creates the internal `generic_params` index of the crate's generic type
+    /// parameters.
+    ///
+    /// The analysis emits type parameters as `Type` definitions whose
+    /// qualified name contains a `$`, but doesn't link them to the item that
+    /// declares them, so they are matched to their item by position later.
+    fn index_generic_params(&mut self, defs: &[Definition]) -> Result<(), KytheError> {
+        for def in defs.iter() {
+            if def.kind != DefKind::Type || !def.qualname.contains('$') {
+                continue;
+            }
+            self.generic_param_ids.insert(def.id);
+            if let Some(name_span) = self.get_byte_span(&def.id, &def.span)? {
+                let file_name = clean(&def.span.file_name);
+                self.generic_params.insert((file_name, name_span.start_byte), def.id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates the internal `foreign_item_modules` index by matching the
+    /// crate's foreign items to the modules that contain them.
+    ///
+    /// The analysis doesn't list foreign items as children of their module
+    /// so the module is found using the item's qualified name.
+    fn index_foreign_items(&mut self, defs: &[Definition]) {
+        let mut module_ids: HashMap<&str, DefId> = HashMap::new();
+        let mut listed_children: HashSet<DefId> = HashSet::new();
+        for def in defs.iter() {
+            if def.kind == DefKind::Mod {
+                module_ids.insert(def.qualname.as_str(), def.id);
+            }
+            listed_children.extend(def.children.iter());
+        }
+
+        for def in defs.iter() {
+            let is_foreign_item = matches!(
+                def.kind,
+                DefKind::ExternType | DefKind::ForeignFunction | DefKind::ForeignStatic
+            );
+            if !is_foreign_item || listed_children.contains(&def.id) {
+                continue;
+            }
+            let module_id = match def.parent {
+                Some(parent_id) => Some(parent_id),
+                None => match def.qualname.rfind("::") {
+                    // Items in the crate's root module
+                    Some(0) => module_ids.get("::").copied(),
+                    Some(index) => module_ids.get(&def.qualname[..index]).copied(),
+                    None => None,
+                },
+            };
+            if let Some(module_id) = module_id {
+                self.foreign_item_modules.insert(def.id, module_id);
+            }
+        }
+    }
+
+    /// Creates the internal `discriminants` index by computing the
+    /// discriminant of each variant declared in the crate's enums.
+    ///
+    /// Variants without an explicit discriminant have the value of the
+    /// previous variant plus one. If the previous value isn't an integer
+    /// literal, the value is written as an expression such as `BASE + 1`.
+    fn index_discriminants(&mut self, defs: &[Definition]) -> Result<(), KytheError> {
+        for def in defs.iter() {
+            if def.kind != DefKind::Enum {
+                continue;
+            }
+            let file_name = clean(&def.span.file_name);
+            let name_span = match self.get_byte_span(&def.id, &def.span)? {
+                Some(span) => span,
+                None => continue,
+            };
+            let variants = match self.offset_index.get_file_contents(&file_name) {
+                Some(file_contents) => find_enum_variants(file_contents, name_span.end_byte),
+                None => continue,
+            };
+
+            // The last explicit discriminant that isn't an integer literal and the
+            // offset from it
+            let mut base: Option<String> = None;
+            let mut offset: i128 = 0;
+            for variant in variants {
+                let value = match variant.discriminant {
+                    Some(expression) => match parse_integer_literal(&expression) {
+                        Some(value) => {
+                            base = None;
+                            offset = value;
+                            value.to_string()
+                        }
+                        None => {
+                            base = Some(expression.clone());
+                            offset = 0;
+                            expression
+                        }
+                    },
+                    None => match &base {
+                        Some(base) => format!("{} + {}", base, offset),
+                        None => offset.to_string(),
+                    },
+                };
+                offset += 1;
+                self.discriminants.insert((file_name.clone(), variant.name_span.start_byte), value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates the internal `type_refs` index so that the types written in
+    /// type aliases can be resolved to their definitions
+    fn index_type_refs(&mut self) -> Result<(), KytheError> {
+        for reference in self.analysis.references.iter() {
+            if reference.kind != RefKind::Type {
+                continue;
+            }
+            if let Some(byte_span) = self.get_byte_span(&reference.target, &reference.span)? {
+                let file_name = clean(&reference.span.file_name);
+                self.type_refs.insert((file_name, byte_span.start_byte), reference.target);
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates the internal `inherited_visibility_ids` index from the items of
+    /// traits and the fields of enum variants. The fields of tuple variants
+    /// aren't listed as children, so they are found by their parent.
+    fn index_inherited_visibility(&mut self, defs: &[Definition]) {
+        let mut parent_ids: HashSet<DefId> = HashSet::new();
+        for def in defs.iter() {
+            if matches!(def.kind, DefKind::Trait | DefKind::TupleVariant | DefKind::StructVariant) {
+                parent_ids.insert(def.id);
+                self.inherited_visibility_ids.extend(def.children.iter());
+            }
+        }
+        for def in defs.iter() {
+            if matches!(def.parent, Some(parent_id) if parent_ids.contains(&parent_id)) {
+                self.inherited_visibility_ids.insert(def.id);
+            }
+        }
+    }
+
+    /// Creates the internal `def_paths` index used to resolve the intra-doc
+    /// links in documentation.
+    ///
+    /// Trait implementation methods are only used if no other definition has
+    /// the same path.
+    fn index_def_paths(&mut self, defs: &[Definition]) {
+        let mut trait_impl_paths: Vec<(String, DefId)> = Vec::new();
+        for def in defs.iter() {
+            // Local variables and generic parameters can't be linked to
+            if def.kind == DefKind::Local || def.qualname.contains('$') || def.qualname == "::" {
+                continue;
+            }
+            match get_def_path(&def.qualname) {
+                Some((path, true)) => trait_impl_paths.push((path, def.id)),
+                Some((path, false)) => {
+                    self.def_paths.insert(path, def.id);
+                }
+                None => {}
+            }
+        }
+        for (path, def_id) in trait_impl_paths {
+            self.def_paths.entry(path).or_insert(def_id);
+        }
+    }
+
+    /// Resolves the path of an intra-doc link found in the documentation of
+    /// `def`. Like rustdoc, the path is resolved relative to the scopes
+    /// containing the documented item, from the innermost to the crate root.
+    fn resolve_doc_link(&self, def: &Definition, path: &str) -> Option<DefId> {
+        if let Some(path) = path.strip_prefix("crate::") {
+            return self.def_paths.get(path).copied();
+        }
+
+        let def_path = get_def_path(&def.qualname).map(|(path, _)| path).unwrap_or_default();
+        let mut scope: Vec<&str> =
+            def_path.split("::").filter(|segment| !segment.is_empty()).collect();
+        let is_type =
+            matches!(def.kind, DefKind::Enum | DefKind::Struct | DefKind::Trait | DefKind::Union);
+        let path = match path.strip_prefix("Self::") {
+            // `Self` is the documented type or the type containing the documented item
+            Some(path) if is_type => path,
+            Some(path) => {
+                scope.pop();
+                path
+            }
+            // Modules are the scope of their own documentation
+            None if def.kind == DefKind::Mod => path.strip_prefix("self::").unwrap_or(path),
+            None => {
+                scope.pop();
+                path.strip_prefix("self::").unwrap_or(path)
+            }
+        };
+
+        loop {
+            let candidate = if scope.is_empty() {
+                path.to_string()
+            } else {
+                format!("{}::{}", scope.join("::"), path)
+            };
+            if let Some(def_id) = self.def_paths.get(&candidate) {
+                return Some(*def_id);
+            }
+            scope.pop()?;
+        }
+    }
+
+    /// Emits a `childof` edge from a foreign item to the module containing
+    /// its `extern` block
+    fn emit_foreign_item_childof(
+        &mut self,
+        def_vname: &VName,
+        def: &Definition,
+    ) -> Result<(), KytheError> {
+        let module_id = match self.foreign_item_modules.remove(&def.id) {
+            Some(module_id) => module_id,
+            None => return Ok(()),
+        };
+        let module_vname = self.get_def_vname(&module_id).ok_or_else(|| {
+            KytheError::IndexerError(format!(
+                "Can't find module vname for foreign item {:?} (\"{}\")",
+                def.id, def.name
+            ))
+        })?;
+        self.emitter.emit_edge(def_vname, &module_vname, "/kythe/edge/childof")
+    }
+
+    /// Emits `tparam.N` edges from a generic item to its type and const
+    /// parameters, which are declared in `file_name`. Lifetime parameters
+    /// aren't modeled.
+    fn emit_generic_param_edges(
+        &mut self,
+        def_vname: &VName,
+        file_name: &str,
+        params: &[GenericParam],
+        file_vname: &VName,
+    ) -> Result<(), KytheError> {
+        let file_name = file_name.to_string();
+        let params = params.iter().filter(|param| param.kind != GenericParamKind::Lifetime);
+        for (param_num, param) in params.enumerate() {
+            let param_vname = if param.kind == GenericParamKind::Const {
+                // The analysis doesn't emit definitions for const parameters so we
+                // generate the node and its anchors ourselves
+                let mut param_vname = def_vname.clone();
+                param_vname.set_signature(format!(
+                    "{}_tparam_{}",
+                    def_vname.get_signature(),
+                    param_num
+                ));
+                self.emitter.emit_fact(&param_vname, "/kythe/node/kind", b"tvar".to_vec())?;
+
+                let mut anchor_vname = param_vname.clone();
+                anchor_vname.set_signature(format!("{}_anchor", param_vname.get_signature()));
+                anchor_vname.set_root(file_vname.get_root().to_string());
+                anchor_vname.set_path(file_vname.get_path().to_string());
+                self.emitter.emit_anchor(
+                    &anchor_vname,
+                    &param_vname,
+                    param.span.start_byte,
+                    param.span.end_byte,
+                )?;
+                self.emit_generic_param_uses(&param_vname, &file_name, &param.name, &param.span)?;
+                param_vname
+            } else {
+                let param_id =
+                    match self.generic_params.get(&(file_name.clone(), param.span.start_byte)) {
+                        Some(param_id) => *param_id,
+                        None => continue,
+                    };
+                match self.get_def_vname(&param_id) {
+                    Some(vname) => vname,
+                    None => continue,
+                }
+            };
+            self.emitter.emit_edge(
+                def_vname,
+                &param_vname,
+                &format!("/kythe/edge/tparam.{}", param_num),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Emits reference anchors for the uses of a const generic parameter within
+    /// the item that declares it. The analysis doesn't report const parameters,
+    /// while the references to type parameters are part of its references.
+    fn emit_generic_param_uses(
+        &mut self,
+        param_vname: &VName,
+        file_name: &str,
+        name: &str,
+        decl_span: &ByteSpan,
+    ) -> Result<(), KytheError> {
+        let uses = match self.offset_index.get_file_contents(file_name) {
+            Some(file_contents) => find_generic_param_uses(file_contents, name, decl_span),
+            None => return Ok(()),
+        };
+        let file_path = match self.file_vnames.get(file_name) {
+            Some(file_vname) => file_vname.get_path().to_string(),
+            None => return Ok(()),
+        };
+        for byte_span in uses {
+            let mut reference_vname = self.krate_vname.clone();
+            reference_vname.set_path(file_path.clone());
+            reference_vname.set_signature(
+                self.create_ref_signature(self.krate_vname.get_signature(), &byte_span),
+            );
+            self.emitter.emit_reference(&reference_vname, param_vname, byte_span)?;
+        }
+        Ok(())
+    }
+
+    /// Emits `param.N` edges from a function to its parameters and tracks the
+    /// parameters as children of the function. A parameter that declares
+    /// several variables, such as `(a, b): (u32, u32)`, has no single node so
+    /// it doesn't get a `param.N` edge, but still counts toward the positions
+    /// of the parameters that follow it.
+    fn emit_parameter_edges(
+        &mut self,
+        def_vname: &VName,
+        def: &Definition,
+    ) -> Result<(), KytheError> {
+        let params = match self.function_params.remove(&def.id) {
+            Some(params) => params,
+            None => return Ok(()),
+        };
+        for (param_num, bindings) in params.iter().enumerate() {
+            for param_id in bindings.iter() {
+                let param_vname = if let Some(vname) = self.definition_vnames.get(param_id) {
+                    // The parameter has already been visited so we can emit the childof edge
+                    self.emitter.emit_edge(vname, def_vname, "/kythe/edge/childof")?;
+                    vname.clone()
+                } else {
+                    let krate_id = self.krate_ids.get(&param_id.krate).ok_or_else(|| {
+                        KytheError::IndexerError(format!(
+                            "Can't find crate for parameter {:?} of function \"{}\"",
+                            param_id, def.name
+                        ))
+                    })?;
+                    self.children_ids.insert(*param_id, def_vname.clone());
+                    self.generate_def_vname(krate_id, param_id)
+                };
+                if bindings.len() == 1 {
+                    self.emitter.emit_edge(
+                        def_vname,
+                        &param_vname,
+                        &format!("/kythe/edge/param.{}", param_num),
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Emit all of the Kythe graph nodes and edges, including anchors for the
     /// definition using the provided VName
     fn emit_definition_node(
         &mut self,
         def_vname: &VName,
-        def: &Def,
+        def: &Definition,
         file_vname: &VName,
     ) -> Result<(), KytheError> {
         // For Fields, we always emit childof edges to their
@@ -506,18 +1143,48 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
         match def.kind {
             DefKind::Const | DefKind::Static => {
                 facts.push(("/kythe/node/kind", b"constant"));
+                self.emit_typed_edge(def_vname, &def.value)?;
             }
             DefKind::Enum => {
                 facts.push(("/kythe/node/kind", b"sum"));
                 facts.push(("/kythe/complete", b"definition"));
                 facts.push(("/kythe/subkind", b"enum"));
+
+                // Record the representation of enums with a `#[repr(...)]` attribute
+                for attribute in def.attributes.iter() {
+                    let repr =
+                        attribute.strip_prefix("repr(").and_then(|value| value.strip_suffix(')'));
+                    if let Some(repr) = repr {
+                        self.emitter.emit_fact(
+                            def_vname,
+                            "/kythe/tag/repr",
+                            repr.as_bytes().to_vec(),
+                        )?;
+                    }
+                }
+            }
+            // Foreign items are declarations inside of an `extern` block
+            DefKind::ExternType => {
+                facts.push(("/kythe/node/kind", b"record"));
+                facts.push(("/kythe/complete", b"incomplete"));
+                self.emit_foreign_item_childof(def_vname, def)?;
+            }
+            DefKind::ForeignFunction => {
+                facts.push(("/kythe/node/kind", b"function"));
+                facts.push(("/kythe/complete", b"incomplete"));
+                self.emit_foreign_item_childof(def_vname, def)?;
+            }
+            DefKind::ForeignStatic => {
+                facts.push(("/kythe/node/kind", b"variable"));
+                facts.push(("/kythe/complete", b"incomplete"));
+                self.emit_typed_edge(def_vname, &def.value)?;
+                self.emit_foreign_item_childof(def_vname, def)?;
             }
             DefKind::Field => {
-                // TODO: Determine how to emit `typed` edges for field based on the `value` on
-                // the definition
                 facts.push(("/kythe/node/kind", b"variable"));
                 facts.push(("/kythe/complete", b"definition"));
                 facts.push(("/kythe/subkind", b"field"));
+                self.emit_typed_edge(def_vname, &def.value)?;
 
                 if let Some(parent_id) = def.parent {
                     // Field definitions come after their parent's definitions so their VName should
@@ -540,10 +1207,9 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
                 }
             }
             DefKind::Function => {
-                // TODO: Handle parameters, emit a defines edge on the entire definition span of
-                // the function
                 facts.push(("/kythe/node/kind", b"function"));
                 facts.push(("/kythe/complete", b"definition"));
+                self.emit_parameter_edges(def_vname, def)?;
             }
             DefKind::Local => {
                 // If the variable is a closure, emit that it is a function
@@ -553,23 +1219,23 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
                 } else {
                     facts.push(("/kythe/node/kind", b"variable"));
                     facts.push(("/kythe/subkind", b"local"));
-                }
+                    self.emit_typed_edge(def_vname, &def.value)?;
 
-                // TODO: Find a way to determine if a variable is only being
-                // declared. The save_analysis uses different index offsets for
-                // difference classes of local variables. One class is for
-                // mutable variables, one is for immutable variables, and one is
-                // for declarations. However these aren't static offsets, they
-                // are based on how many classes were previously seen. The first
-                // class that is seen has an offset of ~2147483647, the second
-                // has an offset of ~1073741827, and the third has an offset of
-                // ~3221225471.
+                    // The save_analysis Id offsets for declarations aren't stable, so
+                    // declarations are identified by the lack of an initializer in the
+                    // source text
+                    if self.is_local_declaration(def)? {
+                        facts.push(("/kythe/complete", b"incomplete"));
+                    }
+                }
+            }
+            DefKind::Macro => {
+                facts.push(("/kythe/node/kind", b"macro"));
             }
             DefKind::Method => {
-                // TODO: Handle parameters, emit a defines edge on the entire definition span of
-                // the method
                 facts.push(("/kythe/node/kind", b"function"));
                 facts.push(("/kythe/complete", b"definition"));
+                self.emit_parameter_edges(def_vname, def)?;
 
                 // If the if-statement logic passes, this is a method on a struct and we emit a
                 // "childof" edge. Otherwise, it is a method on a trait and we
@@ -581,7 +1247,7 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
                     let parent_vname = if let Some(vname) = parent_vname {
                         vname.clone()
                     } else {
-                        // Usually this condition occurs if the save_analysis
+                        // Usually this condition occurs if the analysis
                         // feeds us data that isn't part of our crate.
                         let krate_id = self
                             .krate_ids
@@ -592,10 +1258,34 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
                                     def.id, def.name
                                 ))
                             })?;
-                        self.generate_def_vname(krate_id, method_impl.struct_target.index)
+                        self.generate_def_vname(krate_id, &method_impl.struct_target)
                     };
                     // Emit a childof edge to the parent struct
                     self.emitter.emit_edge(def_vname, &parent_vname, "/kythe/edge/childof")?;
+
+                    // Emit an overrides edge to the trait method being implemented, skipping
+                    // the standard library's traits unless requested
+                    let trait_method = method_impl.trait_method.filter(|trait_method| {
+                        self.emit_std_lib
+                            || !self
+                                .krate_ids
+                                .get(&trait_method.krate)
+                                .map_or(false, |krate_id| is_sysroot_crate(&krate_id.name))
+                    });
+                    if let Some(trait_method) = trait_method {
+                        let trait_method_vname =
+                            self.get_def_vname(&trait_method).ok_or_else(|| {
+                                KytheError::IndexerError(format!(
+                                    "Can't find overridden method vname for method {:?} (\"{}\")",
+                                    def.id, def.name
+                                ))
+                            })?;
+                        self.emitter.emit_edge(
+                            def_vname,
+                            &trait_method_vname,
+                            "/kythe/edge/overrides",
+                        )?;
+                    }
                 }
             }
             DefKind::Mod => {
@@ -625,23 +1315,31 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             DefKind::TupleVariant => {
                 // Check if this is a constant inside of an enum
                 if def.qualname.ends_with(&def.value) {
-                    // TODO: Determine how to get the value of the constant.
-                    // Currently might not be possible using the data from the
-                    // save_analysis
                     facts.push(("/kythe/node/kind", b"constant"));
+
+                    // Emit the value of the constant's discriminant
+                    let file_name = clean(&def.span.file_name);
+                    if let Some(name_span) = self.get_byte_span(&def.id, &def.span)? {
+                        let key = (file_name, name_span.start_byte);
+                        if let Some(value) = self.discriminants.remove(&key) {
+                            self.emitter.emit_fact(def_vname, "/kythe/text", value.into_bytes())?;
+                        }
+                    }
                 } else {
                     facts.push(("/kythe/node/kind", b"record"));
                     facts.push(("/kythe/complete", b"definition"));
                     facts.push(("/kythe/subkind", b"tuplevariant"));
                 }
             }
+            // Generic type parameter
+            DefKind::Type if self.generic_param_ids.contains(&def.id) => {
+                facts.push(("/kythe/node/kind", b"tvar"));
+            }
             DefKind::Type => {
                 facts.push(("/kythe/node/kind", b"talias"));
 
-                // If it aliases a builtin type, emit an aliases edge
-                // TODO: Make this work with custom types
-                if let Some(type_vname) = self.type_vnames.get(&def.value) {
-                    self.emitter.emit_edge(def_vname, type_vname, "/kythe/edge/aliases")?;
+                if let Some(type_vname) = self.resolve_aliased_type(def)? {
+                    self.emitter.emit_edge(def_vname, &type_vname, "/kythe/edge/aliases")?;
                 }
             }
             DefKind::Union => {
@@ -658,11 +1356,44 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             self.emitter.emit_fact(def_vname, fact_name, fact_value.to_vec())?;
         }
 
+        // Emit the MarkedSource used by frontends to render the definition
+        if let Some(krate_id) = self.krate_ids.get(&def.id.krate) {
+            let marked_source = generate_marked_source(def, &krate_id.name);
+            self.emitter.emit_fact(def_vname, "/kythe/code", marked_source.write_to_bytes()?)?;
+        }
+
+        if def.kind != DefKind::Local && !self.generic_param_ids.contains(&def.id) {
+            self.emit_visibility(def_vname, def)?;
+            self.emit_attribute_tags(def_vname, def)?;
+        }
+
+        // Emit the edges to the generic parameters of items that can declare them
+        let is_generic_item = match def.kind {
+            DefKind::Enum
+            | DefKind::Function
+            | DefKind::Method
+            | DefKind::Struct
+            | DefKind::Trait
+            | DefKind::Union => true,
+            DefKind::Type => !self.generic_param_ids.contains(&def.id),
+            _ => false,
+        };
+        if is_generic_item {
+            let file_name = clean(&def.span.file_name);
+            if let (Some(file_contents), Some(name_span)) = (
+                self.offset_index.get_file_contents(&file_name),
+                self.get_byte_span(&def.id, &def.span)?,
+            ) {
+                let params = find_generic_params(file_contents, name_span.end_byte);
+                self.emit_generic_param_edges(def_vname, &file_name, &params, file_vname)?;
+            }
+        }
+
         // Calculate the byte_start and byte_end using the OffsetIndex
-        let file_name = clean(def.span.file_name.to_str().unwrap());
+        let file_name = clean(&def.span.file_name);
         let byte_start = self
             .offset_index
-            .get_byte_offset(&file_name, def.span.line_start.0, def.span.column_start.0)
+            .get_byte_offset(&file_name, def.span.line_start, def.span.column_start)
             .ok_or_else(|| {
                 KytheError::IndexerError(format!(
                     "Failed to get starting offset for definition {}, {:?}",
@@ -671,7 +1402,7 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             })?;
         let byte_end = self
             .offset_index
-            .get_byte_offset(&file_name, def.span.line_end.0, def.span.column_end.0)
+            .get_byte_offset(&file_name, def.span.line_end, def.span.column_end)
             .ok_or_else(|| {
                 KytheError::IndexerError(format!(
                     "Failed to get ending offset for definition {}, {:?}",
@@ -695,6 +1426,28 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             self.emitter.emit_anchor(&anchor_vname, def_vname, byte_start, byte_end)?;
         }
 
+        // Emit a defines anchor over the entire definition so that positions inside
+        // of the definition can be mapped back to it
+        if let Some(item_span) = self.get_item_span(def, &file_name, byte_start, byte_end) {
+            // Track the spans of functions so that call sites can be attributed to them
+            if def.kind == DefKind::Function || def.kind == DefKind::Method {
+                self.function_spans.entry(file_name.clone()).or_default().push((
+                    ByteSpan { start_byte: item_span.start_byte, end_byte: item_span.end_byte },
+                    def_vname.clone(),
+                ));
+            }
+
+            let mut full_anchor_vname = anchor_vname.clone();
+            full_anchor_vname
+                .set_signature(format!("{}_defines_anchor", def_vname.get_signature()));
+            self.emitter.emit_anchor_edge(
+                &full_anchor_vname,
+                def_vname,
+                item_span,
+                "/kythe/edge/defines",
+            )?;
+        }
+
         // If documentation isn't "" also generate a documents node
         // - Emit documentation type node
         // - Emit documents edge from node to def
@@ -703,17 +1456,603 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             let doc_signature = format!("{}_doc", def_vname.get_signature());
             doc_vname.set_signature(doc_signature);
             self.emitter.emit_fact(&doc_vname, "/kythe/node/kind", b"doc".to_vec())?;
+
+            // Intra-doc links that can be resolved are marked up in the text and
+            // linked to their targets with `param.N` edges, in order
+            let docs = def.docs.trim();
+            let links = find_doc_links(docs);
+            let mut resolved_links: Vec<&DocLink> = Vec::new();
+            let mut link_targets: Vec<VName> = Vec::new();
+            for link in links.iter() {
+                let target_vname = self
+                    .resolve_doc_link(def, &link.path)
+                    .and_then(|target_id| self.get_def_vname(&target_id));
+                if let Some(target_vname) = target_vname {
+                    resolved_links.push(link);
+                    link_targets.push(target_vname);
+                }
+            }
             self.emitter.emit_fact(
                 &doc_vname,
                 "/kythe/text",
-                def.docs.trim().as_bytes().to_vec(),
+                generate_doc_markup(docs, &resolved_links).into_bytes(),
             )?;
+            let mut linked_signatures: HashSet<String> = HashSet::new();
+            for (param_num, target_vname) in link_targets.iter().enumerate() {
+                self.emitter.emit_edge(
+                    &doc_vname,
+                    target_vname,
+                    &format!("/kythe/edge/param.{}", param_num),
+                )?;
+                if linked_signatures.insert(target_vname.get_signature().to_string()) {
+                    self.emitter.emit_edge(&doc_vname, target_vname, "/kythe/edge/ref/doc")?;
+                }
+            }
             self.emitter.emit_edge(&doc_vname, def_vname, "/kythe/edge/documents")?;
+
+            // Emit an anchor over the doc comment so that it can be linked to the
+            // definition in the source file
+            if let Some(doc_span) = self.get_doc_comment_span(def, &file_name) {
+                let mut doc_anchor_vname = anchor_vname.clone();
+                doc_anchor_vname.set_signature(format!("{}_doc_anchor", def_vname.get_signature()));
+                self.emitter.emit_anchor_edge(
+                    &doc_anchor_vname,
+                    def_vname,
+                    doc_span,
+                    "/kythe/edge/documents",
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the span of the doc comment lines documenting the definition.
+    /// Outer doc comments (`///`) are found above the definition. Modules can
+    /// also be documented by inner doc comments (`//!`) at the top of their
+    /// body or file.
+    fn get_doc_comment_span(&self, def: &Definition, file_name: &str) -> Option<ByteSpan> {
+        let is_implicit_module = def.kind == DefKind::Mod && self.is_module_implicit(def);
+        if !is_implicit_module {
+            let doc_span =
+                self.find_doc_comment_lines(file_name, def.span.line_start, "///", false);
+            if doc_span.is_some() {
+                return doc_span;
+            }
+        }
+        if def.kind == DefKind::Mod {
+            let first_line = if is_implicit_module { 1 } else { def.span.line_start + 1 };
+            return self.find_doc_comment_lines(file_name, first_line, "//!", true);
+        }
+        None
+    }
+
+    /// Scans the lines of a file for a block of doc comments starting with
+    /// `prefix`. When scanning backward, the scan starts at the line before
+    /// `line`. Attributes may be mixed with the doc comments, and regular
+    /// comments between the block and the starting line are skipped.
+    fn find_doc_comment_lines(
+        &self,
+        file_name: &str,
+        line: u32,
+        prefix: &str,
+        forward: bool,
+    ) -> Option<ByteSpan> {
+        let file_contents = self.offset_index.get_file_contents(file_name)?;
+        let mut doc_span: Option<ByteSpan> = None;
+        let mut line_number = line;
+        loop {
+            if !forward {
+                if line_number <= 1 {
+                    break;
+                }
+                line_number -= 1;
+            }
+            let (line_start, line_end) =
+                match self.offset_index.get_line_span(file_name, line_number) {
+                    Some(line_span) => line_span,
+                    None => break,
+                };
+            if forward {
+                line_number += 1;
+            }
+
+            let text = &file_contents[line_start as usize..line_end as usize];
+            let trimmed = text.trim_start();
+            // Comments starting with four slashes aren't doc comments
+            if trimmed.starts_with(prefix) && !trimmed.starts_with("////") {
+                let start_byte = line_start + (text.len() - trimmed.len()) as u32;
+                let end_byte = line_start + text.trim_end().len() as u32;
+                doc_span = Some(match doc_span {
+                    Some(span) => ByteSpan {
+                        start_byte: span.start_byte.min(start_byte),
+                        end_byte: span.end_byte.max(end_byte),
+                    },
+                    None => ByteSpan { start_byte, end_byte },
+                });
+                continue;
+            }
+            let is_skipped = trimmed.starts_with('#')
+                || (doc_span.is_none()
+                    && (trimmed.starts_with("//") || (forward && trimmed.is_empty())));
+            if !is_skipped {
+                break;
+            }
+        }
+        doc_span
+    }
+
+    /// Returns the outer attributes of a definition written in the source code
+    fn get_source_attributes(&self, def: &Definition) -> Result<Vec<String>, KytheError> {
+        let file_name = clean(&def.span.file_name);
+        Ok(
+            match (
+                self.offset_index.get_file_contents(&file_name),
+                self.get_byte_span(&def.id, &def.span)?,
+            ) {
+                (Some(file_contents), Some(name_span)) => {
+                    find_outer_attributes(file_contents, &name_span)
+                }
+                _ => Vec::new(),
+            },
+        )
+    }
+
+    /// Emits the `/kythe/visibility` fact of a definition, which is `private`
+    /// or the visibility provided by the analysis or written in the source
+    /// code, such as `pub` or `pub(crate)`.
+    ///
+    /// Enum variants, trait items, and their children have the visibility of
+    /// their enum or trait, and trait implementation methods have the
+    /// visibility of their trait, so no fact is emitted for them. Modules
+    /// declared in another file and macros are skipped as well.
+    fn emit_visibility(&mut self, def_vname: &VName, def: &Definition) -> Result<(), KytheError> {
+        let has_visibility = match def.kind {
+            DefKind::Macro | DefKind::TupleVariant | DefKind::StructVariant => false,
+            DefKind::Mod => def.qualname == "::" || !self.is_module_implicit(def),
+            DefKind::Method => !def.qualname.contains(" as "),
+            _ => true,
+        };
+        if !has_visibility || self.inherited_visibility_ids.contains(&def.id) {
+            return Ok(());
+        }
+
+        // The crate's root module is always public
+        let visibility = if def.qualname == "::" {
+            Some("pub".to_string())
+        } else if def.visibility.is_some() {
+            def.visibility.clone()
+        } else {
+            let file_name = clean(&def.span.file_name);
+            match (
+                self.offset_index.get_file_contents(&file_name),
+                self.get_byte_span(&def.id, &def.span)?,
+            ) {
+                (Some(file_contents), Some(name_span)) => {
+                    find_item_visibility(file_contents, &name_span)
+                }
+                _ => None,
+            }
+        };
+        let visibility = visibility.unwrap_or_else(|| "private".to_string());
+        self.emitter.emit_fact(def_vname, "/kythe/visibility", visibility.into_bytes())
+    }
+
+    /// Emits tags for the attributes and qualifiers of a definition:
+    /// - `/kythe/tag/deprecated` and `/kythe/tag/must_use`, whose value is the
+    ///   attribute's message, if any
+    /// - `/kythe/tag/test` for test functions
+    /// - `/kythe/tag/unsafe`, `/kythe/tag/async`, and `/kythe/tag/const` for
+    ///   items with those qualifiers
+    /// - `/kythe/tag/abi` for `extern` functions, whose value is the ABI
+    ///
+    /// The attributes and qualifiers are read from the analysis, which includes
+    /// attributes spanning multiple lines and those generated by macros.
+    fn emit_attribute_tags(
+        &mut self,
+        def_vname: &VName,
+        def: &Definition,
+    ) -> Result<(), KytheError> {
+        let is_function = matches!(def.kind, DefKind::Function | DefKind::Method);
+        let mut tags: Vec<(&str, String)> = Vec::new();
+        for attribute in def.attributes.iter() {
+            let (name, message) = parse_attribute(attribute);
+            let tag = match name {
+                "deprecated" => "/kythe/tag/deprecated",
+                "must_use" => "/kythe/tag/must_use",
+                _ => continue,
+            };
+            if !tags.iter().any(|(existing_tag, _)| *existing_tag == tag) {
+                tags.push((tag, message.unwrap_or_default()));
+            }
+        }
+
+        // Built-in attribute macros such as `#[test]` are removed by the compiler,
+        // so they are only found in the source code. Attribute macros such as
+        // `#[tokio::test]` also mark tests.
+        if is_function {
+            let source_attributes = self.get_source_attributes(def)?;
+            let is_test = def.attributes.iter().chain(source_attributes.iter()).any(|attribute| {
+                let (name, _) = parse_attribute(attribute);
+                name == "test" || name.ends_with("::test")
+            });
+            if is_test {
+                tags.push(("/kythe/tag/test", String::new()));
+            }
+        }
+
+        let qualifiers = parse_item_qualifiers(def.signature.as_deref().unwrap_or_default());
+        if qualifiers.is_unsafe {
+            tags.push(("/kythe/tag/unsafe", String::new()));
+        }
+        if is_function {
+            if qualifiers.is_async {
+                tags.push(("/kythe/tag/async", String::new()));
+            }
+            if qualifiers.is_const {
+                tags.push(("/kythe/tag/const", String::new()));
+            }
+            if let Some(abi) = &qualifiers.abi {
+                tags.push(("/kythe/tag/abi", abi.clone()));
+            }
+        }
+
+        for (tag, value) in tags {
+            self.emitter.emit_fact(def_vname, tag, value.into_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the local variable is declared without being
+    /// initialized, such as `let x: u32;`
+    fn is_local_declaration(&self, def: &Definition) -> Result<bool, KytheError> {
+        let file_name = clean(&def.span.file_name);
+        let name_span = match self.get_byte_span(&def.id, &def.span)? {
+            Some(span) => span,
+            None => return Ok(false),
+        };
+        Ok(match self.offset_index.get_file_contents(&file_name) {
+            Some(file_contents) => is_uninitialized_local(file_contents, &name_span),
+            None => false,
+        })
+    }
+
+    /// Emits a `typed` edge from `def_vname` to the node for `type_string` if
+    /// the type can be resolved
+    fn emit_typed_edge(&mut self, def_vname: &VName, type_string: &str) -> Result<(), KytheError> {
+        if let Some(type_vname) = self.resolve_type(type_string) {
+            self.emitter.emit_edge(def_vname, &type_vname, "/kythe/edge/typed")?;
+        }
+        Ok(())
+    }
+
+    /// Given a type string from the analysis, returns the VName of the
+    /// builtin type or the crate's struct, enum, or union that it names.
+    /// Returns `None` if the type can't be resolved.
+    fn resolve_type(&self, type_string: &str) -> Option<VName> {
+        let type_string = type_string.trim();
+        if let Some(type_vname) = self.type_vnames.get(type_string) {
+            return Some(type_vname.clone());
+        }
+
+        // Remove any leading path qualifiers that refer to the current crate
+        let krate_name = &self.krate_ids.get(&0u32)?.name;
+        let mut path = type_string.trim_start_matches("::");
+        if let Some(stripped) = path.strip_prefix("crate::") {
+            path = stripped;
+        } else if let Some(stripped) =
+            path.strip_prefix(krate_name.as_str()).and_then(|p| p.strip_prefix("::"))
+        {
+            path = stripped;
+        }
+
+        let type_id = self.type_ids.get(path)?;
+        self.get_def_vname(type_id)
+    }
+
+    /// Returns the VName of the type aliased by a type alias. The type is
+    /// parsed from the source code so that generic types can be resolved to
+    /// `tapp` nodes.
+    fn resolve_aliased_type(&mut self, def: &Definition) -> Result<Option<VName>, KytheError> {
+        let file_name = clean(&def.span.file_name);
+        let offset_index = self.offset_index;
+        let syntax = match (
+            offset_index.get_file_contents(&file_name),
+            self.get_byte_span(&def.id, &def.span)?,
+        ) {
+            (Some(file_contents), Some(name_span)) => {
+                find_type_alias_value(file_contents, name_span.end_byte)
+                    .map(|value_span| parse_type(file_contents, &value_span))
+            }
+            _ => None,
+        };
+        match syntax {
+            Some(syntax) => self.resolve_type_syntax(&file_name, &syntax),
+            // Fall back to the builtin types if the source isn't available
+            None => Ok(self.type_vnames.get(&def.value).cloned()),
+        }
+    }
+
+    /// Returns the VName of the type written in the file. Types with generic
+    /// arguments, references, slices, arrays, and tuples are represented by
+    /// `tapp` nodes, which are emitted as needed. Returns `None` if any part of
+    /// the type can't be resolved.
+    fn resolve_type_syntax(
+        &mut self,
+        file_name: &str,
+        syntax: &TypeSyntax,
+    ) -> Result<Option<VName>, KytheError> {
+        let (constructor, args): (Option<VName>, Vec<&TypeSyntax>) = match syntax {
+            TypeSyntax::Path { name, name_span, args } => {
+                let type_id = self.type_refs.get(&(file_name.to_string(), name_span.start_byte));
+                let constructor = match type_id {
+                    Some(type_id) => self.get_def_vname(type_id),
+                    None => self.type_vnames.get(name).cloned(),
+                };
+                (constructor, args.iter().collect())
+            }
+            TypeSyntax::Reference(inner) => {
+                (self.type_vnames.get("reference").cloned(), vec![inner.as_ref()])
+            }
+            TypeSyntax::Slice(inner) => {
+                (self.type_vnames.get("slice").cloned(), vec![inner.as_ref()])
+            }
+            TypeSyntax::Array(inner) => {
+                (self.type_vnames.get("array").cloned(), vec![inner.as_ref()])
+            }
+            TypeSyntax::Tuple(types) => {
+                (self.type_vnames.get("tuple").cloned(), types.iter().collect())
+            }
+            TypeSyntax::Other => return Ok(None),
+        };
+        let constructor = match constructor {
+            Some(vname) => vname,
+            None => return Ok(None),
+        };
+        if args.is_empty() {
+            return Ok(Some(constructor));
+        }
+
+        let mut params = vec![constructor];
+        for arg in args {
+            match self.resolve_type_syntax(file_name, arg)? {
+                Some(vname) => params.push(vname),
+                None => return Ok(None),
+            }
+        }
+        self.emit_tapp_node(&params).map(Some)
+    }
+
+    /// Emits a `tapp` node applying the type constructor in `params[0]` to the
+    /// rest of the parameters and returns its VName
+    fn emit_tapp_node(&mut self, params: &[VName]) -> Result<VName, KytheError> {
+        let signatures: Vec<&str> = params.iter().map(|param| param.get_signature()).collect();
+        let mut tapp_vname = VName::new();
+        tapp_vname.set_corpus(self.unit_vname.get_corpus().to_string());
+        tapp_vname.set_language("rust".to_string());
+        tapp_vname.set_signature(format!("tapp({})", signatures.join(",")));
+
+        // The same type can be used by many aliases but must only be emitted once
+        if self.tapp_signatures.insert(tapp_vname.get_signature().to_string()) {
+            self.emitter.emit_fact(&tapp_vname, "/kythe/node/kind", b"tapp".to_vec())?;
+            for (param_num, param) in params.iter().enumerate() {
+                self.emitter.emit_edge(
+                    &tapp_vname,
+                    param,
+                    &format!("/kythe/edge/param.{}", param_num),
+                )?;
+            }
         }
+        Ok(tapp_vname)
+    }
 
+    /// Returns the VName for the definition with the provided Id. If the
+    /// definition hasn't been visited yet, its VName is generated ahead of
+    /// time. Returns `None` if the definition's crate is unknown.
+    fn get_def_vname(&self, def_id: &DefId) -> Option<VName> {
+        if let Some(vname) = self.definition_vnames.get(def_id) {
+            Some(vname.clone())
+        } else {
+            let krate_id = self.krate_ids.get(&def_id.krate)?;
+            Some(self.generate_def_vname(krate_id, def_id))
+        }
+    }
+
+    /// Returns the span of the entire definition for items that have a body,
+    /// such as functions, structs, and modules. `byte_start` and `byte_end`
+    /// are the offsets of the definition's identifier.
+    fn get_item_span(
+        &self,
+        def: &Definition,
+        file_name: &str,
+        byte_start: u32,
+        byte_end: u32,
+    ) -> Option<ByteSpan> {
+        let file_contents = self.offset_index.get_file_contents(file_name)?;
+        match def.kind {
+            // Implicit modules span their entire file
+            DefKind::Mod if self.is_module_implicit(def) => {
+                Some(ByteSpan { start_byte: 0, end_byte: file_contents.len() as u32 })
+            }
+            DefKind::Enum
+            | DefKind::Function
+            | DefKind::Method
+            | DefKind::Mod
+            | DefKind::Struct
+            | DefKind::Trait
+            | DefKind::Union => find_item_span(
+                file_contents,
+                &ByteSpan { start_byte: byte_start, end_byte: byte_end },
+            ),
+            _ => None,
+        }
+    }
+
+    /// Emit the Kythe graph nodes for the `macro_rules!` macros defined in the
+    /// crate and the `ref/expands` anchors for macro invocations
+    pub fn emit_macros(&mut self) -> Result<(), KytheError> {
+        let file_vnames = self.file_vnames;
+        let offset_index = self.offset_index;
+
+        // The nodes for macros that the analysis reports as definitions have
+        // already been emitted
+        let mut macro_def_ids: HashMap<(String, u32), DefId> = HashMap::new();
+        for def in self.analysis.definitions.iter().filter(|def| def.kind == DefKind::Macro) {
+            if let Some(name_span) = self.get_byte_span(&def.id, &def.span)? {
+                let file_name = clean(&def.span.file_name);
+                macro_def_ids.insert((file_name, name_span.start_byte), def.id);
+            }
+        }
+
+        // A map between a file name and the spans and VNames of the macros defined in
+        // it
+        let mut macro_vnames: HashMap<String, Vec<(ByteSpan, VName)>> = HashMap::new();
+        for file_name in file_vnames.keys() {
+            let file_contents = match offset_index.get_file_contents(file_name) {
+                Some(contents) => contents,
+                None => continue,
+            };
+            for definition in find_macro_definitions(file_contents) {
+                let name_span = definition.name_span;
+                let def_vname = macro_def_ids
+                    .get(&(file_name.clone(), name_span.start_byte))
+                    .and_then(|def_id| self.get_def_vname(def_id));
+                let macro_vname = if let Some(def_vname) = def_vname {
+                    def_vname
+                } else {
+                    let (line, column) = offset_index
+                        .get_line_and_column(file_name, definition.item_span.start_byte)
+                        .ok_or_else(|| {
+                            KytheError::IndexerError(format!(
+                                "Failed to get the position of a macro definition in {}",
+                                file_name
+                            ))
+                        })?;
+                    let macro_vname = self.generate_macro_vname(file_name, line, column);
+                    self.emitter.emit_fact(&macro_vname, "/kythe/node/kind", b"macro".to_vec())?;
+
+                    let mut anchor_vname = macro_vname.clone();
+                    anchor_vname.set_signature(format!("{}_anchor", macro_vname.get_signature()));
+                    self.emitter.emit_anchor(
+                        &anchor_vname,
+                        &macro_vname,
+                        name_span.start_byte,
+                        name_span.end_byte,
+                    )?;
+                    let mut full_anchor_vname = macro_vname.clone();
+                    full_anchor_vname
+                        .set_signature(format!("{}_defines_anchor", macro_vname.get_signature()));
+                    self.emitter.emit_anchor_edge(
+                        &full_anchor_vname,
+                        &macro_vname,
+                        ByteSpan {
+                            start_byte: definition.item_span.start_byte,
+                            end_byte: definition.item_span.end_byte,
+                        },
+                        "/kythe/edge/defines",
+                    )?;
+                    macro_vname
+                };
+                macro_vnames
+                    .entry(file_name.clone())
+                    .or_default()
+                    .push((definition.item_span, macro_vname));
+            }
+        }
+
+        let krate_signature = self.krate_vname.get_signature().to_string();
+        for macro_ref in self.analysis.macro_references.iter() {
+            let file_name = clean(&macro_ref.span.file_name);
+            let file_vname = match file_vnames.get(&file_name) {
+                Some(vname) => vname,
+                None => continue,
+            };
+
+            let callee_span = &macro_ref.callee_span;
+            let callee_file_name = clean(&callee_span.file_name);
+            let target_vname = if file_vnames.contains_key(&callee_file_name) {
+                // The callee span covers the definition of the local macro
+                let callee_start = offset_index.get_byte_offset(
+                    &callee_file_name,
+                    callee_span.line_start,
+                    callee_span.column_start,
+                );
+                let definition = macro_vnames.get(&callee_file_name).and_then(|definitions| {
+                    definitions.iter().find(|(span, _)| {
+                        matches!(callee_start, Some(start) if span.start_byte <= start && start < span.end_byte)
+                    })
+                });
+                match definition {
+                    Some((_, vname)) => vname.clone(),
+                    None => continue,
+                }
+            } else {
+                // The standard library's sources are remapped to /rustc/<commit hash>
+                if callee_file_name.starts_with("/rustc/") && !self.emit_std_lib {
+                    continue;
+                }
+                self.generate_macro_vname(
+                    &callee_file_name,
+                    callee_span.line_start,
+                    callee_span.column_start,
+                )
+            };
+
+            // Place the anchor on the macro's name rather than the entire invocation
+            let span = &macro_ref.span;
+            let start_byte =
+                offset_index.get_byte_offset(&file_name, span.line_start, span.column_start);
+            let end_byte = offset_index.get_byte_offset(&file_name, span.line_end, span.column_end);
+            let byte_span = match (start_byte, end_byte) {
+                (Some(start_byte), Some(end_byte)) => offset_index
+                    .get_file_contents(&file_name)
+                    .and_then(|contents| find_macro_name(contents, start_byte))
+                    .unwrap_or(ByteSpan { start_byte, end_byte }),
+                _ => continue,
+            };
+
+            let mut reference_vname = self.krate_vname.clone();
+            reference_vname.set_path(file_vname.get_path().to_string());
+            reference_vname.set_signature(self.create_ref_signature(&krate_signature, &byte_span));
+            self.emitter.emit_anchor_edge(
+                &reference_vname,
+                &target_vname,
+                byte_span,
+                "/kythe/edge/ref/expands",
+            )?;
+        }
         Ok(())
     }
 
+    /// Creates the VName for the macro defined at `line` and `column` of the
+    /// file. Macros defined in the standard library are placed in
+    /// `sysroot_corpus` if it is provided, at the same path as the file nodes
+    /// emitted when indexing the sysroot crates. Other macros defined outside
+    /// of the crate are placed in the CompilationUnit's corpus.
+    fn generate_macro_vname(&self, file_name: &str, line: u32, column: u32) -> VName {
+        let mut macro_vname = match self.file_vnames.get(file_name) {
+            Some(file_vname) => file_vname.clone(),
+            None => {
+                let mut vname = VName::new();
+                match (&self.sysroot_corpus, get_library_path(file_name)) {
+                    (Some(sysroot_corpus), Some(library_path)) => {
+                        vname.set_corpus(sysroot_corpus.corpus.clone());
+                        vname.set_root(sysroot_corpus.root.clone());
+                        vname.set_path(library_path.to_string());
+                    }
+                    _ => {
+                        vname.set_corpus(self.unit_vname.get_corpus().to_string());
+                        vname.set_path(file_name.to_string());
+                    }
+                }
+                vname
+            }
+        };
+        macro_vname.set_language("rust".to_string());
+        macro_vname.set_signature(format!("macro_{}_{}", line, column));
+        macro_vname
+    }
+
     /// Emit the Kythe edges for cross references for imports in this crate
     pub fn emit_import_xrefs(&mut self) -> Result<(), KytheError> {
         assert!(!self.krate_ids.is_empty());
@@ -724,14 +2063,33 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
         let template_vname = self.krate_vname.clone();
         let krate_signature = template_vname.get_signature();
 
-        for reference in &imports {
-            // If there is no reference id, we can't emit a cross reference
-            if reference.ref_id.is_none() {
+        // Glob imports don't have a reference id so the imported module is found
+        // using the reference to the last segment of the path
+        let mut module_refs: HashMap<(String, u32), DefId> = HashMap::new();
+        for reference in self.analysis.references.iter() {
+            if reference.kind != RefKind::Mod {
                 continue;
             }
+            if let Some(byte_span) = self.get_byte_span(&reference.target, &reference.span)? {
+                let file_name = clean(&reference.span.file_name);
+                module_refs.insert((file_name, byte_span.end_byte), reference.target);
+            }
+        }
 
-            // Currently this is only known to support `Use` ImportKinds
-            if reference.kind != rls_data::ImportKind::Use {
+        for reference in &imports {
+            let ref_id = match reference.kind {
+                ImportKind::Use => reference.target,
+                ImportKind::GlobUse => self.get_glob_import_module(reference, &module_refs),
+                // `extern crate` items don't have a target, so we refer to the root module of the crate with the same name
+                ImportKind::ExternCrate => self
+                    .krate_ids
+                    .iter()
+                    .find(|(_, krate_id)| krate_id.name == reference.name)
+                    .map(|(krate_num, _)| DefId { krate: *krate_num, index: 0 }),
+            };
+
+            // If there is no reference id, we can't emit a cross reference
+            if ref_id.is_none() {
                 continue;
             }
 
@@ -739,7 +2097,7 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             let span = &reference.span;
 
             // Get the CrateId for the referenced crate
-            let ref_id = reference.ref_id.unwrap();
+            let ref_id = ref_id.unwrap();
             let krate_id = self.krate_ids.get(&ref_id.krate);
             if krate_id.is_none() {
                 // This is a little bit of chicken and egg here. We could try
@@ -761,15 +2119,20 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             }
             let krate_id = krate_id.unwrap();
 
-            if krate_id.name == "std" && !self.emit_std_lib {
+            if is_sysroot_crate(&krate_id.name) && !self.emit_std_lib {
                 continue;
             }
 
-            // Create VName for target of reference
-            let definition_vname = self.generate_def_vname(krate_id, ref_id.index);
+            // Create VName for target of reference. `extern crate` items refer to the
+            // crate's package node
+            let definition_vname = if reference.kind == ImportKind::ExternCrate {
+                self.generate_crate_vname(krate_id)
+            } else {
+                self.generate_def_vname(krate_id, &ref_id)
+            };
 
             // Create VName for the reference node
-            let file_name = clean(span.file_name.to_str().unwrap());
+            let file_name = clean(&span.file_name);
             let file_vname = self.file_vnames.get(&file_name);
             if file_vname.is_none() {
                 // Emit a diagostic node to the top level file for the current crate
@@ -793,11 +2156,112 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             // Create signature based on span
             reference_vname.set_signature(self.create_ref_signature(krate_signature, &byte_span));
 
-            self.emitter.emit_reference(&reference_vname, &definition_vname, byte_span)?;
+            self.emitter.emit_anchor_edge(
+                &reference_vname,
+                &definition_vname,
+                byte_span,
+                "/kythe/edge/ref/imports",
+            )?;
         }
         Ok(())
     }
 
+    /// Emits references from the `mod name;` declarations in the crate to the
+    /// modules they declare and to the files containing the modules
+    pub fn emit_module_declarations(&mut self) -> Result<(), KytheError> {
+        let mut crate_root = None;
+        let mut module_files: HashMap<String, DefId> = HashMap::new();
+        for def in self.analysis.definitions.iter() {
+            let file_name = clean(&def.span.file_name);
+            if def.qualname == "::" {
+                crate_root = Some(file_name);
+            } else if self.is_module_implicit(def) {
+                module_files.insert(file_name, def.id);
+            }
+        }
+
+        // The analysis may already reference the module from its declaration
+        let mut module_refs: HashSet<(String, u32)> = HashSet::new();
+        for reference in self.analysis.references.iter() {
+            if reference.kind == RefKind::Mod {
+                if let Some(byte_span) = self.get_byte_span(&reference.target, &reference.span)? {
+                    let file_name = clean(&reference.span.file_name);
+                    module_refs.insert((file_name, byte_span.start_byte));
+                }
+            }
+        }
+
+        let mut file_names: Vec<String> = self.file_vnames.keys().cloned().collect();
+        file_names.sort();
+        let krate_signature = self.krate_vname.get_signature().to_string();
+        for file_name in file_names {
+            let declarations = match self.offset_index.get_file_contents(&file_name) {
+                Some(file_contents) => find_module_declarations(file_contents),
+                None => continue,
+            };
+            let is_mod_rs = crate_root.as_deref() == Some(file_name.as_str())
+                || Path::new(&file_name).file_name() == Some(OsStr::new("mod.rs"));
+
+            for declaration in declarations.iter() {
+                let module_file = get_module_file_candidates(&file_name, is_mod_rs, declaration)
+                    .into_iter()
+                    .find(|candidate| self.file_vnames.contains_key(candidate));
+                let module_file = match module_file {
+                    Some(module_file) => module_file,
+                    // The module may be excluded by a `#[cfg]` attribute
+                    None => continue,
+                };
+                let file_vname = self.file_vnames[&module_file].clone();
+                let module_vname = module_files
+                    .get(&module_file)
+                    .and_then(|module_id| self.get_def_vname(module_id));
+
+                let name_span = &declaration.name_span;
+                let mut anchor_vname = self.krate_vname.clone();
+                anchor_vname.set_path(self.file_vnames[&file_name].get_path().to_string());
+                anchor_vname.set_signature(self.create_ref_signature(&krate_signature, name_span));
+                let byte_span =
+                    ByteSpan { start_byte: name_span.start_byte, end_byte: name_span.end_byte };
+
+                if module_refs.contains(&(file_name.clone(), name_span.start_byte)) {
+                    // The anchor has already been emitted for the module reference
+                    self.emitter.emit_edge(&anchor_vname, &file_vname, "/kythe/edge/ref/file")?;
+                } else if let Some(module_vname) = module_vname {
+                    self.emitter.emit_reference(&anchor_vname, &module_vname, byte_span)?;
+                    self.emitter.emit_edge(&anchor_vname, &file_vname, "/kythe/edge/ref/file")?;
+                } else {
+                    self.emitter.emit_anchor_edge(
+                        &anchor_vname,
+                        &file_vname,
+                        byte_span,
+                        "/kythe/edge/ref/file",
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the Id of the module imported by a glob import such as
+    /// `use foo::bar::*`, using the reference to the path segment before the
+    /// `*`
+    fn get_glob_import_module(
+        &self,
+        import: &Import,
+        module_refs: &HashMap<(String, u32), DefId>,
+    ) -> Option<DefId> {
+        let file_name = clean(&import.span.file_name);
+        let file_contents = self.offset_index.get_file_contents(&file_name)?;
+        let glob_start = self.offset_index.get_byte_offset(
+            &file_name,
+            import.span.line_start,
+            import.span.column_start,
+        )?;
+        let path = file_contents.get(..glob_start as usize)?.trim_end();
+        let path = path.strip_suffix("::")?.trim_end();
+        module_refs.get(&(file_name, path.len() as u32)).copied()
+    }
+
     // We need separate functions for import xrefs and normal xrefs due to
     // different types being looped through
     /// Emit the Kythe edges for cross references in this crate
@@ -805,7 +2269,7 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
         assert!(!self.krate_ids.is_empty());
 
         // We must clone to avoid double borrowing "self"
-        let refs = self.analysis.refs.clone();
+        let refs = self.analysis.references.clone();
 
         let template_vname = self.krate_vname.clone();
         let krate_signature = template_vname.get_signature();
@@ -813,7 +2277,7 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
         for reference in &refs {
             let mut reference_vname = template_vname.clone();
             let span = &reference.span;
-            let ref_id = reference.ref_id;
+            let ref_id = reference.target;
 
             // Get the CrateId for the referenced crate
             let krate_id = self.krate_ids.get(&ref_id.krate);
@@ -836,15 +2300,15 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             }
             let krate_id = krate_id.unwrap();
 
-            if krate_id.name == "std" && !self.emit_std_lib {
+            if is_sysroot_crate(&krate_id.name) && !self.emit_std_lib {
                 continue;
             }
 
             // Create VName for target of reference
-            let target_vname = self.generate_def_vname(krate_id, ref_id.index);
+            let target_vname = self.generate_def_vname(krate_id, &ref_id);
 
             // Create VName for the reference node
-            let file_name = clean(span.file_name.to_str().unwrap());
+            let file_name = clean(&span.file_name);
             let file_vname = self.file_vnames.get(&file_name);
             if file_vname.is_none() {
                 self.emitter.emit_diagnostic(
@@ -857,7 +2321,7 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             }
             reference_vname.set_path(file_vname.unwrap().get_path().to_string());
 
-            let byte_span = self.get_byte_span(&reference.ref_id, span)?;
+            let byte_span = self.get_byte_span(&ref_id, span)?;
             if byte_span.is_none() {
                 continue;
             }
@@ -866,36 +2330,95 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
             // Create signature based on span
             reference_vname.set_signature(self.create_ref_signature(krate_signature, &byte_span));
 
+            // Calls to functions and methods get a separate anchor over the whole
+            // call expression, which is attributed to the enclosing function
+            let call = if reference.kind == RefKind::Function {
+                self.get_call(&file_name, &byte_span)
+            } else {
+                None
+            };
+
             self.emitter.emit_reference(&reference_vname, &target_vname, byte_span)?;
+            if let Some((call_span, caller_vname)) = call {
+                let mut call_vname = reference_vname.clone();
+                call_vname.set_signature(format!(
+                    "{}_call_{}_{}",
+                    krate_signature, call_span.start_byte, call_span.end_byte
+                ));
+                self.emitter.emit_anchor_edge(
+                    &call_vname,
+                    &target_vname,
+                    call_span,
+                    "/kythe/edge/ref/call",
+                )?;
+                self.emitter.emit_edge(&call_vname, &caller_vname, "/kythe/edge/childof")?;
+            }
         }
         Ok(())
     }
 
+    /// If the function reference at `byte_span` is the callee of a call,
+    /// returns the span of the call expression and the VName of the innermost
+    /// function or method that contains it
+    fn get_call(&self, file_name: &str, byte_span: &ByteSpan) -> Option<(ByteSpan, VName)> {
+        let file_contents = self.offset_index.get_file_contents(file_name)?;
+        let call_span = find_call_span(file_contents, byte_span)?;
+        let caller_vname = self
+            .function_spans
+            .get(file_name)?
+            .iter()
+            .filter(|(span, _)| {
+                span.start_byte <= call_span.start_byte && call_span.end_byte <= span.end_byte
+            })
+            .min_by_key(|(span, _)| span.end_byte - span.start_byte)
+            .map(|(_, vname)| vname.clone())?;
+        Some((call_span, caller_vname))
+    }
+
     /// Creates a signature for a definition with the given id
     fn create_ref_signature(&self, signature: &str, byte_span: &ByteSpan) -> String {
         format!("{}_ref_{}_{}", signature, byte_span.start_byte, byte_span.end_byte)
     }
 
-    /// Creates a signature for a definition with the given id
-    fn generate_def_vname(&self, crate_id: &rls_analysis::CrateId, index: u32) -> VName {
+    /// Creates a VName for the definition with the given id. The signature is
+    /// based on the crate's name and the definition's canonical path if it is
+    /// known, such as `shapes#type::shapes::Square`, and on the crate's
+    /// disambiguator and the definition's index otherwise. The crate's
+    /// disambiguator is left out of canonical signatures because it changes
+    /// whenever the crate's dependencies or compiler options change.
+    ///
+    /// Canonical signatures don't include the crate's version either, because
+    /// rustc isn't told the versions of crates: Cargo only passes them in the
+    /// environment of the crate being compiled, and never for its dependencies,
+    /// so references couldn't use the same signature as the definition. As a
+    /// result, the versions of a crate indexed in the same corpus share the
+    /// nodes of definitions with the same path. Index-based signatures keep
+    /// versions apart through the disambiguator, since Cargo passes each
+    /// version its own `-C metadata`.
+    fn generate_def_vname(&self, crate_id: &CrateId, def_id: &DefId) -> VName {
         let mut crate_vname = self.generate_crate_vname(crate_id);
         let crate_signature = crate_vname.get_signature().to_owned();
-        crate_vname.set_signature(format!("{}_def_{}", crate_signature, index));
+        // The sysroot crates are indexed from the toolchain's save-analysis, which
+        // doesn't have canonical paths
+        let is_sysroot = self.sysroot_corpus.is_some() && is_sysroot_crate(&crate_id.name);
+        let canonical_path =
+            if is_sysroot { None } else { self.analysis.canonical_paths.get(def_id) };
+        let signature = match canonical_path {
+            Some(path) => format!("{}#{}", crate_id.name, path),
+            None => format!("{}_def_{}", crate_signature, def_id.index),
+        };
+        crate_vname.set_signature(signature);
         crate_vname
     }
 
     /// Return byte span for an analysis span
-    fn get_byte_span(
-        &self,
-        ref_id: &rls_data::Id,
-        span: &rls_data::SpanData,
-    ) -> Result<Option<ByteSpan>, KytheError> {
+    fn get_byte_span(&self, ref_id: &DefId, span: &Span) -> Result<Option<ByteSpan>, KytheError> {
         // Get byte span
-        let file_name = clean(span.file_name.to_str().unwrap());
+        let file_name = clean(&span.file_name);
         let start_byte =
-            self.offset_index.get_byte_offset(&file_name, span.line_start.0, span.column_start.0);
+            self.offset_index.get_byte_offset(&file_name, span.line_start, span.column_start);
 
-        // If the start byte is none, then the save_analysis is giving information about
+        // If the start byte is none, then the analysis is giving information about
         // standard library files and we should skip
         if start_byte.is_none() {
             return Ok(None);
@@ -904,7 +2427,7 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
         let start_byte = start_byte.unwrap();
         let end_byte = self
             .offset_index
-            .get_byte_offset(&file_name, span.line_end.0, span.column_end.0)
+            .get_byte_offset(&file_name, span.line_end, span.column_end)
             .ok_or_else(|| {
                 KytheError::IndexerError(format!(
                     "Failed to get ending offset for reference {:?}",
@@ -916,6 +2439,42 @@ impl<'a, 'b> CrateAnalyzer<'a, 'b> {
     }
 }
 
+/// The qualifiers written before the keyword of an item, such as the ones in
+/// `const unsafe extern "C" fn`
+#[derive(Default)]
+struct ItemQualifiers {
+    is_async: bool,
+    is_const: bool,
+    is_unsafe: bool,
+    /// The ABI of an `extern` item, which is `C` if it isn't written
+    abi: Option<String>,
+}
+
+/// Parses the qualifiers at the start of a definition's signature, such as
+/// `unsafe extern "system" fn run()`. The signature doesn't include the
+/// definition's visibility.
+fn parse_item_qualifiers(signature: &str) -> ItemQualifiers {
+    let mut qualifiers = ItemQualifiers::default();
+    let mut words = signature.split_whitespace().peekable();
+    while let Some(word) = words.next() {
+        match word {
+            "async" => qualifiers.is_async = true,
+            "const" => qualifiers.is_const = true,
+            "unsafe" => qualifiers.is_unsafe = true,
+            "extern" => {
+                let abi = match words.next_if(|abi| abi.starts_with('"')) {
+                    Some(abi) => abi.trim_matches('"').to_string(),
+                    None => "C".to_string(),
+                };
+                qualifiers.abi = Some(abi);
+            }
+            // The qualifiers end at the item's keyword
+            _ => break,
+        }
+    }
+    qualifiers
+}
+
 /// Convert a VName from analysis_rust_proto to a VName from storage_rust_proto
 fn analysis_to_storage_vname(analysis_vname: &analysis_rust_proto::VName) -> VName {
     let mut vname = VName::new();
@@ -926,3 +2485,59 @@ fn analysis_to_storage_vname(analysis_vname: &analysis_rust_proto::VName) -> VNa
     vname.set_language(analysis_vname.get_language().to_string());
     vname
 }
+
+/// Converts the qualified name of a definition into the path used to refer to
+/// it in intra-doc links, such as `module::Type::method` for
+/// `<module::Type<T>>::method`. Also returns whether the definition is a method
+/// in a trait implementation. Returns `None` if the path can't be determined.
+fn get_def_path(qualname: &str) -> Option<(String, bool)> {
+    let qualname = qualname.trim_start_matches("::");
+    let impl_path = match qualname.strip_prefix('<') {
+        Some(impl_path) => impl_path,
+        None => return Some((qualname.to_string(), false)),
+    };
+    let (self_type, name) = impl_path.rsplit_once(">::")?;
+    let (self_type, is_trait_impl) = match self_type.split_once(" as ") {
+        Some((self_type, _)) => (self_type, true),
+        None => (self_type, false),
+    };
+    // Generic arguments aren't part of the path used in links
+    let self_type = match self_type.find('<') {
+        Some(index) => &self_type[..index],
+        None => self_type,
+    };
+    Some((format!("{}::{}", self_type.trim_start_matches("::"), name), is_trait_impl))
+}
+
+/// Returns the possible paths of the file containing the module declared in
+/// `parent_file`. `is_mod_rs` is whether the parent file is the crate root or
+/// a `mod.rs` file, whose child modules are in the same directory.
+fn get_module_file_candidates(
+    parent_file: &str,
+    is_mod_rs: bool,
+    declaration: &ModuleDeclaration,
+) -> Vec<String> {
+    let parent_path = Path::new(parent_file);
+    let parent_directory = parent_path.parent().unwrap_or_else(|| Path::new(""));
+    let mut directory = parent_directory.to_path_buf();
+    if !is_mod_rs {
+        if let Some(stem) = parent_path.file_stem() {
+            directory.push(stem);
+        }
+    }
+    for module_name in declaration.inline_modules.iter() {
+        directory.push(module_name);
+    }
+
+    let candidates = match &declaration.path {
+        // Paths are relative to the parent file's directory unless the
+        // declaration is inside of an inline module
+        Some(path) if declaration.inline_modules.is_empty() => vec![parent_directory.join(path)],
+        Some(path) => vec![directory.join(path)],
+        None => vec![
+            directory.join(format!("{}.rs", declaration.name)),
+            directory.join(&declaration.name).join("mod.rs"),
+        ],
+    };
+    candidates.iter().filter_map(|candidate| candidate.to_str()).map(clean).collect()
+}
@@ -0,0 +1,166 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::KytheError;
+
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// The options passed to rustc that affect how the crate is analyzed
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CrateOptions {
+    /// The name of the crate
+    pub crate_name: String,
+    /// The path of the crate's root source file
+    pub crate_root: String,
+    /// The names and paths of the crates passed with `--extern`
+    pub externs: Vec<(String, String)>,
+    /// The values of the `-C metadata` options
+    pub metadata: Vec<String>,
+}
+
+/// Parses the rustc arguments stored in a CompilationUnit. The crate name
+/// defaults to the name of the root source file, like rustc.
+pub fn parse_arguments(arguments: &[String]) -> Result<CrateOptions, KytheError> {
+    let mut options = CrateOptions::default();
+    let mut crate_name = None;
+    let mut index = 0;
+    while index < arguments.len() {
+        let argument = arguments[index].as_str();
+        index += 1;
+
+        // Options can be passed as `--option value` or `--option=value`
+        let (flag, inline_value) = match argument.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+            _ => (argument, None),
+        };
+        let (flag, inline_value) = match flag.strip_prefix("-C") {
+            Some(value) if !value.is_empty() => ("-C", Some(&argument[2..])),
+            _ => (flag, inline_value),
+        };
+        // `--edition` and `--cfg` aren't recorded, but their values are skipped so
+        // they aren't mistaken for the root source file
+        let takes_value = matches!(
+            flag,
+            "--crate-name" | "--edition" | "--cfg" | "--extern" | "-C" | "--codegen"
+        );
+        let value = match (takes_value, inline_value) {
+            (false, _) => None,
+            (true, Some(value)) => Some(value),
+            (true, None) => {
+                index += 1;
+                Some(arguments.get(index - 1).map(String::as_str).ok_or_else(|| {
+                    KytheError::IndexerError(format!("Missing value for argument {}", flag))
+                })?)
+            }
+        };
+
+        match (flag, value) {
+            ("--crate-name", Some(value)) => crate_name = Some(value.to_string()),
+            ("--extern", Some(value)) => {
+                let (name, path) = value.split_once('=').unwrap_or((value, ""));
+                options.externs.push((name.to_string(), path.to_string()));
+            }
+            ("-C" | "--codegen", Some(value)) => {
+                if let Some(metadata) = value.strip_prefix("metadata=") {
+                    options.metadata.push(metadata.to_string());
+                }
+            }
+            _ if !argument.starts_with('-') && argument.ends_with(".rs") => {
+                if options.crate_root.is_empty() {
+                    options.crate_root = argument.to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if options.crate_root.is_empty() {
+        return Err(KytheError::IndexerError(
+            "The crate's root source file could not be found in the arguments".to_string(),
+        ));
+    }
+    options.crate_name = crate_name.unwrap_or_else(|| {
+        let stem = Path::new(&options.crate_root).file_stem().and_then(|stem| stem.to_str());
+        stem.unwrap_or_default().replace('-', "_")
+    });
+    Ok(options)
+}
+
+/// Generates the disambiguator of a crate from its name and the values that
+/// distinguish it from other crates with the same name
+pub fn generate_disambiguator(name: &str, values: &[String]) -> (u64, u64) {
+    let mut hasher = Sha256::new();
+    hasher.update(name.as_bytes());
+    for value in values.iter() {
+        hasher.update(value.as_bytes());
+    }
+    let digest = hasher.finalize();
+    let mut first = [0u8; 8];
+    let mut second = [0u8; 8];
+    first.copy_from_slice(&digest[..8]);
+    second.copy_from_slice(&digest[8..16]);
+    (u64::from_le_bytes(first), u64::from_le_bytes(second))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_arguments(arguments: &[&str]) -> Vec<String> {
+        arguments.iter().map(|argument| argument.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_arguments_works() {
+        let arguments = to_arguments(&[
+            "--",
+            "rustc",
+            "src/main.rs",
+            "--crate-name=test_crate",
+            "--edition",
+            "2021",
+            "--cfg",
+            "feature=\"std\"",
+            "--cfg=unix",
+            "-Cmetadata=abc",
+            "--extern",
+            "serde=libserde.rlib",
+            "--test",
+        ]);
+        let options = parse_arguments(&arguments).unwrap();
+        assert_eq!(
+            options,
+            CrateOptions {
+                crate_name: "test_crate".to_string(),
+                crate_root: "src/main.rs".to_string(),
+                externs: vec![("serde".to_string(), "libserde.rlib".to_string())],
+                metadata: vec!["abc".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_arguments_uses_defaults() {
+        let options =
+            parse_arguments(&to_arguments(&["src/my-crate.rs", "-C", "opt-level=3"])).unwrap();
+        assert_eq!(options.crate_name, "my_crate");
+        assert!(options.metadata.is_empty());
+    }
+
+    #[test]
+    fn parse_arguments_requires_crate_root() {
+        assert!(parse_arguments(&to_arguments(&["--crate-name", "test"])).is_err());
+    }
+}
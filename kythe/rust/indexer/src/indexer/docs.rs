@@ -0,0 +1,192 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// An intra-doc link found in a documentation comment
+pub struct DocLink {
+    /// The byte offset of the link's opening bracket in the documentation
+    pub start: usize,
+    /// The byte offset after the end of the link in the documentation
+    pub end: usize,
+    /// The text displayed for the link
+    pub label: String,
+    /// The path the link points to, without backticks or disambiguators
+    pub path: String,
+}
+
+/// Finds the intra-doc links in the documentation of an item. Shortcut links
+/// such as ``[`Foo`]`` and inline links such as `[text](Foo::method)` are
+/// supported. Links inside of code blocks and inline code are ignored, as are
+/// links whose destination isn't a path.
+pub fn find_doc_links(docs: &str) -> Vec<DocLink> {
+    let bytes = docs.as_bytes();
+    let mut links = Vec::new();
+    let mut in_code_block = false;
+    let mut index = 0;
+    while index < bytes.len() {
+        // Code blocks are fenced by lines starting with ```
+        if index == 0 || bytes[index - 1] == b'\n' {
+            let line_end = docs[index..].find('\n').map_or(docs.len(), |offset| index + offset);
+            if docs[index..line_end].trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+                index = line_end + 1;
+                continue;
+            }
+        }
+        if in_code_block {
+            index += 1;
+            continue;
+        }
+
+        match bytes[index] {
+            b'\\' => index += 2,
+            // Skip over inline code
+            b'`' => {
+                index = match docs[index + 1..].find('`') {
+                    Some(offset) => index + offset + 2,
+                    None => docs.len(),
+                };
+            }
+            b'[' => {
+                let (link, end) = parse_doc_link(docs, index);
+                links.extend(link);
+                index = end;
+            }
+            _ => index += 1,
+        }
+    }
+    links
+}
+
+/// Parses the link starting at the opening bracket at `start`. Returns the
+/// link, if any, and the byte offset to continue searching from.
+fn parse_doc_link(docs: &str, start: usize) -> (Option<DocLink>, usize) {
+    let label_end = match docs[start + 1..].find(['[', ']', '\n']) {
+        Some(offset) if docs.as_bytes()[start + 1 + offset] == b']' => start + 1 + offset,
+        _ => return (None, start + 1),
+    };
+    let label = &docs[start + 1..label_end];
+    let rest = &docs[label_end + 1..];
+
+    let (destination, end) = if rest.starts_with('(') {
+        match rest.find([')', '\n']) {
+            Some(close) if rest.as_bytes()[close] == b')' => {
+                (&rest[1..close], label_end + close + 2)
+            }
+            _ => return (None, label_end + 1),
+        }
+    } else if rest.starts_with('[') {
+        // Reference links aren't supported, so skip over the reference too
+        let end = match rest.find(']') {
+            Some(close) => label_end + close + 2,
+            None => label_end + 1,
+        };
+        return (None, end);
+    } else if rest.starts_with(':') {
+        // The definition of a link reference
+        return (None, label_end + 1);
+    } else {
+        (label, label_end + 1)
+    };
+
+    match normalize_link_path(destination) {
+        Some(path) => (Some(DocLink { start, end, label: label.to_string(), path }), end),
+        None => (None, label_end + 1),
+    }
+}
+
+/// Strips the backticks and disambiguators from a link's destination, such as
+/// in ``[`struct@Foo`]`` or `[foo()]`. Returns `None` if the destination isn't
+/// a path.
+fn normalize_link_path(destination: &str) -> Option<String> {
+    let mut path = destination.trim().trim_matches('`');
+    if let Some(index) = path.find('@') {
+        path = &path[index + 1..];
+    }
+    path = path.trim_end_matches("()").trim_end_matches('!');
+
+    let is_path = !path.is_empty()
+        && !path.starts_with(':')
+        && !path.ends_with(':')
+        && path.chars().all(|c| c.is_alphanumeric() || c == '_' || c == ':');
+    if is_path {
+        Some(path.to_string())
+    } else {
+        None
+    }
+}
+
+/// Rewrites the documentation using Kythe's markup for links. The label of
+/// each link in `links` is surrounded with brackets and every other bracket and
+/// backslash is escaped. `links` must be sorted by their position in the text.
+pub fn generate_doc_markup(docs: &str, links: &[&DocLink]) -> String {
+    let mut markup = String::with_capacity(docs.len());
+    let mut position = 0;
+    for link in links.iter() {
+        escape_into(&mut markup, &docs[position..link.start]);
+        markup.push('[');
+        escape_into(&mut markup, &link.label);
+        markup.push(']');
+        position = link.end;
+    }
+    escape_into(&mut markup, &docs[position..]);
+    markup
+}
+
+/// Appends the text to the markup, escaping the characters with a special
+/// meaning
+fn escape_into(markup: &mut String, text: &str) {
+    for c in text.chars() {
+        if matches!(c, '[' | ']' | '\\') {
+            markup.push('\\');
+        }
+        markup.push(c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_doc_links_works() {
+        let docs = "Returns a [Foo] or a [`bar::baz`].\n\nSee [the method](Foo::run) and \
+                    [`struct@Foo`] or [sum()], but not [x][Foo], [`Vec<T>`] or \
+                    [home](https://kythe.io).\n\n```\nlet x = v[0];\n```\nAlso `a[i]`.";
+        let links = find_doc_links(docs);
+        let found: Vec<(&str, &str)> =
+            links.iter().map(|link| (link.label.as_str(), link.path.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Foo", "Foo"),
+                ("`bar::baz`", "bar::baz"),
+                ("the method", "Foo::run"),
+                ("`struct@Foo`", "Foo"),
+                ("sum()", "sum"),
+            ]
+        );
+        assert_eq!(&docs[links[2].start..links[2].end], "[the method](Foo::run)");
+    }
+
+    #[test]
+    fn generate_doc_markup_works() {
+        let docs = "Uses [Foo] and [text](bar) but not [baz] or a\\b.";
+        let links = find_doc_links(docs);
+        let resolved: Vec<&DocLink> = links.iter().filter(|link| link.path != "baz").collect();
+        assert_eq!(
+            generate_doc_markup(docs, &resolved),
+            "Uses [Foo] and [text] but not \\[baz\\] or a\\\\b."
+        );
+    }
+}
@@ -99,6 +99,21 @@ impl<'a> EntryEmitter<'a> {
         anchor_vname: &VName,
         target_vname: &VName,
         byte_span: ByteSpan,
+    ) -> Result<(), KytheError> {
+        self.emit_anchor_edge(anchor_vname, target_vname, byte_span, "/kythe/edge/ref")
+    }
+
+    /// Creates an anchor node covering `byte_span` with an edge of `edge_kind`
+    /// to the target and emits it.
+    ///
+    /// # Errors
+    /// If an error occurs while writing the entry, an error is returned.
+    pub fn emit_anchor_edge(
+        &mut self,
+        anchor_vname: &VName,
+        target_vname: &VName,
+        byte_span: ByteSpan,
+        edge_kind: &str,
     ) -> Result<(), KytheError> {
         self.emit_fact(anchor_vname, "/kythe/node/kind", b"anchor".to_vec())?;
         self.emit_fact(
@@ -111,7 +126,7 @@ impl<'a> EntryEmitter<'a> {
             "/kythe/loc/end",
             byte_span.end_byte.to_string().into_bytes().to_vec(),
         )?;
-        self.emit_edge(anchor_vname, target_vname, "/kythe/edge/ref")
+        self.emit_edge(anchor_vname, target_vname, edge_kind)
     }
 
     /// Creates a diagnostic node with a tagged edge from an anchor or file
@@ -0,0 +1,317 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::model::{DefKind, Definition};
+use common_rust_proto::{MarkedSource, MarkedSource_Kind};
+
+/// Builds the MarkedSource for a definition in the crate named `krate_name`.
+///
+/// The MarkedSource consists of the definition's keyword, its identifier
+/// qualified by the crate and the items that contain it, and its type. The
+/// parameter list and return type of functions are taken from the
+/// definition's signature if it is available and the definition's value
+/// otherwise.
+pub fn generate_marked_source(def: &Definition, krate_name: &str) -> MarkedSource {
+    let is_type_param = def.kind == DefKind::Type && def.qualname.contains('$');
+    let keyword = match def.kind {
+        DefKind::Const => "const ",
+        DefKind::Enum => "enum ",
+        DefKind::Function | DefKind::ForeignFunction | DefKind::Method => "fn ",
+        DefKind::Macro => "macro_rules! ",
+        DefKind::Mod => "mod ",
+        DefKind::Static | DefKind::ForeignStatic => "static ",
+        DefKind::Struct => "struct ",
+        DefKind::Trait => "trait ",
+        DefKind::ExternType => "type ",
+        DefKind::Type if !is_type_param => "type ",
+        DefKind::Union => "union ",
+        _ => "",
+    };
+
+    let mut root = MarkedSource::new();
+    if !keyword.is_empty() {
+        root.mut_child().push(new_node(MarkedSource_Kind::BOX, keyword));
+    }
+
+    // The qualified name of the definition
+    let mut name = MarkedSource::new();
+    let context = get_context(def, krate_name);
+    if !context.is_empty() {
+        let mut context_node = MarkedSource::new();
+        context_node.set_kind(MarkedSource_Kind::CONTEXT);
+        context_node.set_post_child_text("::".to_string());
+        context_node.set_add_final_list_token(true);
+        for segment in context.iter() {
+            context_node.mut_child().push(new_node(MarkedSource_Kind::IDENTIFIER, segment));
+        }
+        name.mut_child().push(context_node);
+    }
+    name.mut_child().push(new_node(MarkedSource_Kind::IDENTIFIER, &def.name));
+    root.mut_child().push(name);
+
+    match def.kind {
+        DefKind::Function | DefKind::ForeignFunction | DefKind::Method => {
+            let signature = def.signature.as_deref().unwrap_or(&def.value);
+            if let Some((params, return_type)) = split_function_signature(signature) {
+                let mut param_node = new_node(MarkedSource_Kind::PARAMETER, "(");
+                param_node.set_post_child_text(", ".to_string());
+                param_node.set_post_text(")".to_string());
+                for param in params.iter() {
+                    param_node.mut_child().push(new_node(MarkedSource_Kind::BOX, param));
+                }
+                root.mut_child().push(param_node);
+                if let Some(return_type) = return_type {
+                    root.mut_child().push(new_node(MarkedSource_Kind::BOX, " -> "));
+                    root.mut_child().push(new_node(MarkedSource_Kind::TYPE, &return_type));
+                }
+            }
+        }
+        // Closures don't have a type that can be written
+        DefKind::Const
+        | DefKind::Field
+        | DefKind::ForeignStatic
+        | DefKind::Local
+        | DefKind::Static
+            if !def.value.is_empty() && !def.value.contains("closure@") =>
+        {
+            root.mut_child().push(new_node(MarkedSource_Kind::BOX, ": "));
+            root.mut_child().push(new_node(MarkedSource_Kind::TYPE, &def.value));
+        }
+        DefKind::Type if !is_type_param && !def.value.is_empty() => {
+            root.mut_child().push(new_node(MarkedSource_Kind::BOX, " = "));
+            root.mut_child().push(new_node(MarkedSource_Kind::TYPE, &def.value));
+        }
+        _ => {}
+    }
+
+    root
+}
+
+/// Creates a MarkedSource node of the given kind with `pre_text`
+fn new_node(kind: MarkedSource_Kind, pre_text: &str) -> MarkedSource {
+    let mut node = MarkedSource::new();
+    node.set_kind(kind);
+    node.set_pre_text(pre_text.to_string());
+    node
+}
+
+/// Returns the names of the crate and items containing the definition, based
+/// on its qualified name. Local variables and the crate's root module don't
+/// have any context.
+fn get_context(def: &Definition, krate_name: &str) -> Vec<String> {
+    if def.kind == DefKind::Local || def.qualname == "::" {
+        return Vec::new();
+    }
+
+    // Generic type parameters have their Id appended to their qualified name
+    let qualname = match def.qualname.find('$') {
+        Some(index) => &def.qualname[..index],
+        None => def.qualname.as_str(),
+    };
+    let mut segments = split_path(qualname);
+    // The last segment is the name of the definition itself
+    segments.pop();
+
+    let mut context = vec![krate_name.to_string()];
+    for segment in segments.into_iter().filter(|segment| !segment.is_empty()) {
+        // The qualified names of inherent methods start with `<Type>`
+        let segment = match segment.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+            Some(inner) if !inner.contains(" as ") => inner,
+            _ => segment,
+        };
+        context.push(segment.to_string());
+    }
+    context
+}
+
+/// Splits a path on the `::` separators that aren't nested inside of angle
+/// brackets, such as the one in `<Type as Trait>::method`
+fn split_path(path: &str) -> Vec<&str> {
+    let bytes = path.as_bytes();
+    let mut segments = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'<' => depth += 1,
+            b'>' => depth -= 1,
+            b':' if depth == 0 && bytes.get(index + 1) == Some(&b':') => {
+                segments.push(&path[start..index]);
+                index += 2;
+                start = index;
+                continue;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    segments.push(&path[start..]);
+    segments
+}
+
+/// Splits a function signature such as `fn add(lhs: u32, rhs: u32) -> u32`
+/// into its parameters and return type. The return type is `None` if the
+/// function returns `()`.
+fn split_function_signature(signature: &str) -> Option<(Vec<String>, Option<String>)> {
+    let bytes = signature.as_bytes();
+
+    // Skip over the generic parameters to find the parameter list
+    let mut angle_depth = 0;
+    let mut open = None;
+    for (index, byte) in bytes.iter().enumerate() {
+        match byte {
+            b'<' => angle_depth += 1,
+            // Ignore the arrow in `Fn() -> T` bounds
+            b'>' if index > 0 && bytes[index - 1] == b'-' => {}
+            b'>' => angle_depth -= 1,
+            b'(' if angle_depth == 0 => {
+                open = Some(index);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let open = open?;
+
+    // Split the parameters on the commas that aren't nested inside of their types
+    let mut params = Vec::new();
+    let mut depth = 0;
+    let mut param_start = open + 1;
+    let mut close = None;
+    for (index, byte) in bytes.iter().enumerate().skip(open) {
+        match byte {
+            b'(' | b'[' | b'<' => depth += 1,
+            // Ignore the arrow in `Fn() -> T` parameters
+            b'>' if bytes[index - 1] == b'-' => {}
+            b')' | b']' | b'>' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(index);
+                    break;
+                }
+            }
+            b',' if depth == 1 => {
+                params.push(signature[param_start..index].trim().to_string());
+                param_start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    let close = close?;
+    let last_param = signature[param_start..close].trim();
+    if !last_param.is_empty() {
+        params.push(last_param.to_string());
+    }
+
+    // The return type ends at the where clause or the function's body
+    let mut return_type = &signature[close + 1..];
+    for terminator in [" where ", "{", ";"] {
+        if let Some(index) = return_type.find(terminator) {
+            return_type = &return_type[..index];
+        }
+    }
+    let return_type = match return_type.trim().strip_prefix("->") {
+        Some(return_type) if return_type.trim() != "()" => Some(return_type.trim().to_string()),
+        _ => None,
+    };
+    Some((params, return_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::model::{DefId, Span};
+
+    fn test_def(kind: DefKind, name: &str, qualname: &str, value: &str) -> Definition {
+        Definition {
+            kind,
+            id: DefId { krate: 0, index: 0 },
+            span: Span {
+                file_name: "main.rs".to_string(),
+                line_start: 1,
+                column_start: 1,
+                line_end: 1,
+                column_end: 1,
+            },
+            name: name.to_string(),
+            qualname: qualname.to_string(),
+            value: value.to_string(),
+            parent: None,
+            children: Vec::new(),
+            declaration: None,
+            docs: String::new(),
+            signature: None,
+            attributes: Vec::new(),
+            visibility: None,
+        }
+    }
+
+    /// Renders the MarkedSource as plain text
+    fn render(node: &MarkedSource) -> String {
+        let mut text = node.get_pre_text().to_string();
+        let children = node.get_child();
+        for (index, child) in children.iter().enumerate() {
+            text.push_str(&render(child));
+            if index + 1 < children.len() || node.get_add_final_list_token() {
+                text.push_str(node.get_post_child_text());
+            }
+        }
+        text.push_str(node.get_post_text());
+        text
+    }
+
+    #[test]
+    fn function_marked_source_works() {
+        let def = test_def(DefKind::Function, "add", "::math::add", "fn (u32, u32) -> u32");
+        assert_eq!(
+            render(&generate_marked_source(&def, "calc")),
+            "fn calc::math::add(u32, u32) -> u32"
+        );
+
+        let mut def = test_def(DefKind::Method, "run", "<Runner>::run", "fn <F> (&Self, F) -> ()");
+        assert_eq!(render(&generate_marked_source(&def, "calc")), "fn calc::Runner::run(&Self, F)");
+
+        def.signature =
+            Some("fn run<F: Fn(u8) -> u8>(&self, callback: F) where F: Copy".to_string());
+        assert_eq!(
+            render(&generate_marked_source(&def, "calc")),
+            "fn calc::Runner::run(&self, callback: F)"
+        );
+    }
+
+    #[test]
+    fn variable_marked_source_works() {
+        let def = test_def(DefKind::Field, "count", "::Counter::count", "u32");
+        assert_eq!(render(&generate_marked_source(&def, "calc")), "calc::Counter::count: u32");
+
+        let def = test_def(DefKind::Local, "total", "total$3", "u64");
+        assert_eq!(render(&generate_marked_source(&def, "calc")), "total: u64");
+    }
+
+    #[test]
+    fn type_marked_source_works() {
+        let def = test_def(DefKind::Type, "Id", "::Id", "u32");
+        assert_eq!(render(&generate_marked_source(&def, "calc")), "type calc::Id = u32");
+
+        let def = test_def(DefKind::Type, "T", "::Wrapper::T$12", "");
+        assert_eq!(render(&generate_marked_source(&def, "calc")), "calc::Wrapper::T");
+
+        let def = test_def(DefKind::Method, "fmt", "<Id as std::fmt::Debug>::fmt", "fn () -> ()");
+        assert_eq!(
+            render(&generate_marked_source(&def, "calc")),
+            "fn calc::<Id as std::fmt::Debug>::fmt()"
+        );
+    }
+}
@@ -13,8 +13,17 @@
 // limitations under the License.
 
 pub mod analyzers;
+pub mod arguments;
+pub mod docs;
 pub mod entries;
+pub mod marked_source;
+pub mod model;
 pub mod offset;
+pub mod rustdoc;
+pub mod save_analysis;
+pub mod scanner;
+pub mod scip;
+pub mod sysroot;
 
 use crate::error::KytheError;
 use crate::providers::FileProvider;
@@ -22,7 +31,13 @@ use crate::writer::KytheWriter;
 
 use analysis_rust_proto::*;
 use analyzers::UnitAnalyzer;
-use std::path::PathBuf;
+use model::AnalysisSource;
+use rustdoc::RustdocSource;
+use save_analysis::SaveAnalysisSource;
+use serde_json::Value;
+use sysroot::SysrootCorpus;
+
+pub use save_analysis::DEF_PATHS_SUFFIX;
 
 /// A data structure for indexing CompilationUnits
 pub struct KytheIndexer<'a> {
@@ -35,54 +50,109 @@ impl<'a> KytheIndexer<'a> {
         Self { writer }
     }
 
-    /// Accepts a CompilationUnit and the directory for analysis files and
-    /// indexes the CompilationUnit
+    /// Accepts a CompilationUnit and indexes it using the save-analysis
+    /// stored in the CompilationUnit by the extractor. If the CompilationUnit
+    /// only contains the crate's rustdoc JSON, the crate is indexed from it,
+    /// which provides definitions, documentation, and implementations but no
+    /// references.
+    ///
+    /// If `semantic_signatures` is true, definitions are given signatures based
+    /// on their crate's name and their canonical paths instead of their
+    /// save-analysis indices, using the definition paths recorded in the
+    /// CompilationUnit by the extractor's `--def_paths` mode. Definitions
+    /// without a recorded path, such as local variables, keep their index-based
+    /// signatures.
+    ///
+    /// If `sysroot_corpus` is provided, references to the sysroot crates, such
+    /// as `std` and `core`, target the nodes emitted when indexing their
+    /// sources in that corpus.
     pub fn index_cu(
         &mut self,
         unit: &CompilationUnit,
         provider: &mut dyn FileProvider,
         emit_std_lib: bool,
         tbuiltin_std_corpus: bool,
+        semantic_signatures: bool,
+        sysroot_corpus: Option<&SysrootCorpus>,
     ) -> Result<(), KytheError> {
-        let analysis_file = Self::get_analysis_file(unit, provider)?;
-        let mut generator = UnitAnalyzer::new(unit, self.writer, provider)?;
+        let mut source = find_analysis_source(unit, provider)?;
+        self.index_cu_with_source(
+            source.as_mut(),
+            unit,
+            provider,
+            emit_std_lib,
+            tbuiltin_std_corpus,
+            semantic_signatures,
+            sysroot_corpus,
+        )
+    }
 
-        if let Some(analysis) = rls_analysis::deserialize_crate_data(&analysis_file) {
-            // First, create file nodes for all of the source files in the CompilationUnit
-            generator.handle_files()?;
-            // Then index the crate
-            generator.index_crate(analysis, emit_std_lib, tbuiltin_std_corpus)?;
-        } else {
-            return Err(KytheError::IndexerError(
-                "Failed to deserialize save-analysis file".to_string(),
-            ));
+    /// Accepts a CompilationUnit and indexes the analysis of its crate
+    /// produced by `source`
+    ///
+    /// If `semantic_signatures` is false, the canonical paths provided by the
+    /// source are ignored and definitions are given signatures based on their
+    /// indices.
+    #[allow(clippy::too_many_arguments)]
+    pub fn index_cu_with_source(
+        &mut self,
+        source: &mut dyn AnalysisSource,
+        unit: &CompilationUnit,
+        provider: &mut dyn FileProvider,
+        emit_std_lib: bool,
+        tbuiltin_std_corpus: bool,
+        semantic_signatures: bool,
+        sysroot_corpus: Option<&SysrootCorpus>,
+    ) -> Result<(), KytheError> {
+        let mut analysis = source.analyze(unit, provider)?;
+        if !semantic_signatures {
+            analysis.canonical_paths.clear();
         }
+        let mut generator = UnitAnalyzer::new(unit, self.writer, provider)?;
+
+        // First, create file nodes for all of the source files in the CompilationUnit
+        generator.handle_files()?;
+        // Then index the crate
+        generator.index_crate(
+            analysis,
+            emit_std_lib,
+            tbuiltin_std_corpus,
+            sysroot_corpus.cloned(),
+        )?;
 
         // We must flush the writer each time to ensure that all entries get written
         self.writer.flush()?;
         Ok(())
     }
+}
 
-    fn get_analysis_file(
-        c_unit: &CompilationUnit,
-        provider: &mut dyn FileProvider,
-    ) -> Result<String, KytheError> {
-        for required_input in c_unit.get_required_input() {
-            let input_path = required_input.get_info().get_path();
-            let input_path_buf = PathBuf::from(input_path);
-
-            // save_analysis files are JSON files
-            if let Some(os_str) = input_path_buf.extension() {
-                if let Some("json") = os_str.to_str() {
-                    let hash = required_input.get_info().get_digest();
-                    let file_bytes = provider.contents(input_path, hash)?;
-                    let file_string = String::from_utf8_lossy(&file_bytes);
-                    return Ok(file_string.to_string());
-                }
-            }
+/// Returns the source of the analysis stored in the CompilationUnit by the
+/// extractor. The save-analysis is preferred because rustdoc JSON has no
+/// references, so rustdoc JSON is only used if there is no save-analysis.
+/// Each JSON file is read and parsed once.
+fn find_analysis_source(
+    unit: &CompilationUnit,
+    provider: &mut dyn FileProvider,
+) -> Result<Box<dyn AnalysisSource>, KytheError> {
+    let mut rustdoc_json = None;
+    for required_input in unit.get_required_input() {
+        let input_path = required_input.get_info().get_path();
+        if !input_path.ends_with(".json") || input_path.ends_with(DEF_PATHS_SUFFIX) {
+            continue;
+        }
+        let hash = required_input.get_info().get_digest();
+        let json: Value = serde_json::from_slice(&provider.contents(input_path, hash)?)?;
+        if !rustdoc::is_rustdoc_json(&json) {
+            return Ok(Box::new(SaveAnalysisSource::new(json)?));
         }
-        Err(KytheError::IndexerError(
+        if rustdoc_json.is_none() {
+            rustdoc_json = Some(json);
+        }
+    }
+    match rustdoc_json {
+        Some(json) => Ok(Box::new(RustdocSource::new(json)?)),
+        None => Err(KytheError::IndexerError(
             "The save-analysis file could not be found in the Compilation Unit".to_string(),
-        ))
+        )),
     }
 }
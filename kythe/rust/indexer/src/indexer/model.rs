@@ -0,0 +1,225 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The analysis of a crate that the indexer emits Kythe graph information
+//! for. The model doesn't depend on the tool that analyzed the crate, so any
+//! [AnalysisSource] can be indexed by the same code.
+//!
+//! The model follows the conventions of the save_analysis, which was the
+//! indexer's first source:
+//! - The qualified name of the crate's root module is `::`
+//! - The qualified names of local variables and generic type parameters
+//!   contain a `$` followed by a number, such as `x$12`
+//! - The qualified names of methods in trait implementations have the form
+//!   `<Type as Trait>::method`
+//! - Fieldless enum variants are `TupleVariant` definitions whose qualified
+//!   name ends with their value
+//! - Closures are local variables whose value contains `closure@`
+
+use crate::error::KytheError;
+use crate::providers::FileProvider;
+
+use analysis_rust_proto::CompilationUnit;
+use std::collections::HashMap;
+
+/// Identifies a definition. The `krate` is 0 for the crate being analyzed and
+/// the position of the crate in [CrateAnalysis::external_crates] plus one for
+/// external crates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DefId {
+    pub krate: u32,
+    pub index: u32,
+}
+
+/// Identifies a crate by its name and a pair of numbers that distinguish it
+/// from other crates with the same name
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrateId {
+    pub name: String,
+    pub disambiguator: (u64, u64),
+}
+
+/// A range of text in a source file. Lines and columns are one-indexed and
+/// columns are counted in characters. The end of the span is exclusive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    /// The file's path, as it appears in the CompilationUnit
+    pub file_name: String,
+    pub line_start: u32,
+    pub column_start: u32,
+    pub line_end: u32,
+    pub column_end: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DefKind {
+    Const,
+    Enum,
+    ExternType,
+    Field,
+    ForeignFunction,
+    ForeignStatic,
+    Function,
+    Local,
+    Macro,
+    Method,
+    Mod,
+    Static,
+    Struct,
+    StructVariant,
+    Trait,
+    Tuple,
+    TupleVariant,
+    Type,
+    Union,
+}
+
+/// A definition in the crate or in one of its dependencies
+#[derive(Clone, Debug)]
+pub struct Definition {
+    pub kind: DefKind,
+    pub id: DefId,
+    /// The span of the definition's name
+    pub span: Span,
+    pub name: String,
+    /// The definition's path, such as `::shapes::Square` or
+    /// `<shapes::Square as shapes::Shape>::area`
+    pub qualname: String,
+    /// The type of variables, the type aliased by type aliases, and the text of
+    /// the signature of functions
+    pub value: String,
+    /// The definition containing fields, trait items, and the fields of enum
+    /// variants
+    pub parent: Option<DefId>,
+    pub children: Vec<DefId>,
+    /// The trait item implemented by an item of a trait implementation, which
+    /// may be defined in another crate
+    pub declaration: Option<DefId>,
+    /// The text of the definition's doc comments, without their prefixes
+    pub docs: String,
+    /// The declaration of a function as written in the source, such as
+    /// `fn add(lhs: u32, rhs: u32) -> u32`, if it is known. It starts with the
+    /// function's qualifiers, such as `unsafe`, but not its visibility.
+    pub signature: Option<String>,
+    /// The attributes of the definition other than its doc comments, such as
+    /// `repr(u8)`
+    pub attributes: Vec<String>,
+    /// The visibility of the definition, such as `pub` or `pub(crate)`, if the
+    /// source provides it. Otherwise, the visibility is read from the source
+    /// code and definitions without one are private.
+    pub visibility: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefKind {
+    Function,
+    Mod,
+    Type,
+    Variable,
+}
+
+/// A reference to a definition
+#[derive(Clone, Debug)]
+pub struct Reference {
+    pub kind: RefKind,
+    pub span: Span,
+    pub target: DefId,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportKind {
+    /// `use foo::Bar`
+    Use,
+    /// `use foo::*`
+    GlobUse,
+    /// `extern crate foo`
+    ExternCrate,
+}
+
+/// An imported name
+#[derive(Clone, Debug)]
+pub struct Import {
+    pub kind: ImportKind,
+    /// The span of the imported name, or of the `*` for glob imports
+    pub span: Span,
+    /// The imported definition. Glob imports and `extern crate` items don't
+    /// have a target.
+    pub target: Option<DefId>,
+    pub name: String,
+}
+
+/// An `impl` block
+#[derive(Clone, Debug)]
+pub struct Implementation {
+    pub id: u32,
+    /// The span of the name of the type being implemented on, such as
+    /// `Wrapper` in `impl<T> Wrapper<T>`, if it is known
+    pub span: Option<Span>,
+    /// The items defined in the implementation
+    pub children: Vec<DefId>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RelationKind {
+    /// `from` is the type being implemented on and `to` is the trait, if any
+    Impl { implementation: u32 },
+    /// `from` is the supertrait and `to` is the trait that extends it
+    SuperTrait,
+}
+
+/// A relation between two definitions. A side is `None` if it isn't a
+/// definition, such as the trait of an inherent implementation.
+#[derive(Clone, Debug)]
+pub struct Relation {
+    pub kind: RelationKind,
+    pub from: Option<DefId>,
+    pub to: Option<DefId>,
+}
+
+/// An invocation of a macro
+#[derive(Clone, Debug)]
+pub struct MacroReference {
+    /// The span of the entire invocation
+    pub span: Span,
+    /// The span of the macro's definition
+    pub callee_span: Span,
+}
+
+/// The analysis of a crate
+#[derive(Clone, Debug)]
+pub struct CrateAnalysis {
+    pub krate: CrateId,
+    /// The crates that the crate depends on, numbered from 1
+    pub external_crates: Vec<CrateId>,
+    pub definitions: Vec<Definition>,
+    pub references: Vec<Reference>,
+    pub imports: Vec<Import>,
+    pub implementations: Vec<Implementation>,
+    pub relations: Vec<Relation>,
+    pub macro_references: Vec<MacroReference>,
+    /// A map between a definition's Id and its canonical namespace and path,
+    /// such as `type::shapes::Square`, for the definitions that have one
+    pub canonical_paths: HashMap<DefId, String>,
+}
+
+/// A tool that analyzes the crate in a CompilationUnit
+pub trait AnalysisSource {
+    /// Returns the analysis of the crate in `unit`, reading the
+    /// CompilationUnit's files using `provider`
+    fn analyze(
+        &mut self,
+        unit: &CompilationUnit,
+        provider: &mut dyn FileProvider,
+    ) -> Result<CrateAnalysis, KytheError>;
+}
@@ -17,12 +17,13 @@ use std::collections::HashMap;
 /// Maintains an index of byte offsets for files
 pub struct OffsetIndex {
     files: HashMap<String, Vec<LineIndex>>,
+    contents: HashMap<String, String>,
 }
 
 impl OffsetIndex {
     /// Initialize an empty OffsetIndex
     pub fn new() -> Self {
-        Self { files: HashMap::new() }
+        Self { files: HashMap::new(), contents: HashMap::new() }
     }
 
     /// Adds a new file using the provided string content
@@ -54,8 +55,41 @@ impl OffsetIndex {
             offset += line_bytes.len();
         }
 
-        // Add the new file to the HashMap
+        // Add the new file to the HashMaps
         self.files.insert(file_name.to_string(), line_indices);
+        self.contents.insert(file_name.to_string(), file_content.to_string());
+    }
+
+    /// Get the contents of a file in the index. Returns None if the file isn't
+    /// present in the index.
+    pub fn get_file_contents(&self, file_name: &str) -> Option<&str> {
+        self.contents.get(file_name).map(|contents| contents.as_str())
+    }
+
+    /// Get the line and column of a byte offset in a file. Returns None if the
+    /// file isn't present in the index or if the offset is out of bounds.
+    pub fn get_line_and_column(&self, file_name: &str, byte_offset: u32) -> Option<(u32, u32)> {
+        let contents = self.contents.get(file_name)?;
+        let prefix = contents.get(..byte_offset as usize)?;
+        let line = prefix.matches('\n').count() + 1;
+        let column = prefix.rsplit('\n').next()?.chars().count() + 1;
+        Some((line as u32, column as u32))
+    }
+
+    /// Get the starting and ending byte offsets of a line in a file, excluding
+    /// the line terminator. Returns None if the file isn't present in the
+    /// index or if the line is out of bounds.
+    pub fn get_line_span(&self, file_name: &str, line: u32) -> Option<(u32, u32)> {
+        let line_indices = self.files.get(file_name)?;
+        let contents = self.contents.get(file_name)?;
+        let line_index = line_indices.get((line as usize).checked_sub(1)?)?;
+        let next_offset = match line_indices.get(line as usize) {
+            Some(next_line_index) => next_line_index.offset,
+            None => contents.len() as u32,
+        };
+        let line_contents = &contents[line_index.offset as usize..next_offset as usize];
+        let line_length = line_contents.trim_end_matches(['\n', '\r']).len() as u32;
+        Some((line_index.offset, line_index.offset + line_length))
     }
 
     /// Get the byte offset for a line and column in a file. Returns None if the
@@ -197,4 +231,37 @@ mod tests {
         // "N" in "New". An emoji is 4 bytes
         assert_eq!(index.get_byte_offset("file.txt", 2, 1), Some(23));
     }
+
+    #[test]
+    fn file_contents_are_stored() {
+        let mut index = OffsetIndex::new();
+        let file_content = "Test string\nNew";
+        index.add_file("file.txt", file_content);
+        assert_eq!(index.get_file_contents("file.txt"), Some(file_content));
+        assert_eq!(index.get_file_contents("missing.txt"), None);
+    }
+
+    #[test]
+    fn line_span_works() {
+        let mut index = OffsetIndex::new();
+        let file_content = "🥳 First\r\nSecond\nThird";
+        index.add_file("file.txt", file_content);
+        assert_eq!(index.get_line_span("file.txt", 1), Some((0, 10)));
+        assert_eq!(index.get_line_span("file.txt", 2), Some((12, 18)));
+        assert_eq!(index.get_line_span("file.txt", 3), Some((19, 24)));
+        assert_eq!(index.get_line_span("file.txt", 4), None);
+        assert_eq!(index.get_line_span("file.txt", 0), None);
+    }
+
+    #[test]
+    fn line_and_column_works() {
+        let mut index = OffsetIndex::new();
+        let file_content = "🥳 This code works!\nNew";
+        index.add_file("file.txt", file_content);
+        assert_eq!(index.get_line_and_column("file.txt", 0), Some((1, 1)));
+        // "T" in "This". An emoji is 4 bytes
+        assert_eq!(index.get_line_and_column("file.txt", 5), Some((1, 3)));
+        assert_eq!(index.get_line_and_column("file.txt", 22), Some((2, 1)));
+        assert_eq!(index.get_line_and_column("file.txt", 100), None);
+    }
 }
@@ -0,0 +1,1035 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::KytheError;
+use crate::providers::FileProvider;
+
+use super::analyzers::ByteSpan;
+use super::arguments::{generate_disambiguator, parse_arguments, CrateOptions};
+use super::model::{
+    AnalysisSource, CrateAnalysis, CrateId, DefId, DefKind, Definition, Implementation, Relation,
+    RelationKind, Span,
+};
+use super::offset::OffsetIndex;
+use super::scanner::{find_generic_params, find_item_name, GenericParamKind};
+
+use analysis_rust_proto::CompilationUnit;
+use path_clean::clean;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// The value of the fields missing from a rustdoc JSON value
+static NULL: Value = Value::Null;
+
+/// The parts of a rustdoc JSON file read by the indexer. The contents of
+/// items are kept as JSON values because their format changes between
+/// versions of rustdoc.
+#[derive(Deserialize)]
+struct RustdocCrate {
+    /// The Id of the crate's root module
+    root: Value,
+    index: HashMap<String, Item>,
+    /// The crates that the crate depends on, keyed by their crate number
+    external_crates: HashMap<String, ExternalCrate>,
+}
+
+#[derive(Deserialize)]
+struct ExternalCrate {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Item {
+    /// The crate number of the item, which is 0 for the crate being documented
+    crate_id: u32,
+    name: Option<String>,
+    span: Option<ItemSpan>,
+    visibility: Value,
+    docs: Option<String>,
+    #[serde(default)]
+    attrs: Vec<Value>,
+    /// The kind of the item and its contents, such as `{"struct": {..}}`
+    inner: Value,
+}
+
+/// The span of an item. Lines are one-indexed and columns are zero-indexed.
+#[derive(Deserialize)]
+struct ItemSpan {
+    filename: String,
+    begin: (u32, u32),
+    end: (u32, u32),
+}
+
+/// An implementation found in a module, which is indexed once all of the
+/// types it could be implemented on are known
+struct PendingImpl {
+    key: String,
+    /// The path of the module containing the implementation
+    module_path: String,
+    /// The position of the implementation among the module's implementations
+    number: usize,
+}
+
+/// Reads the rustdoc JSON of a crate, which the extractor stores in a
+/// CompilationUnit in place of the save-analysis when run with
+/// `--rustdoc_json`.
+///
+/// Rustdoc JSON doesn't cover everything the save-analysis does: it has no
+/// references, imports, or local variables. The crates' disambiguators are
+/// generated from their names and rustc arguments, so they don't match those
+/// of crates indexed from a save-analysis, and their definitions only share
+/// nodes when both are indexed with `--semantic_signatures`.
+pub struct RustdocSource {
+    krate: RustdocCrate,
+}
+
+impl RustdocSource {
+    /// Create a new source from the parsed contents of a rustdoc JSON file
+    pub fn new(json: Value) -> Result<Self, KytheError> {
+        Ok(Self { krate: serde_json::from_value(json)? })
+    }
+}
+
+impl AnalysisSource for RustdocSource {
+    fn analyze(
+        &mut self,
+        unit: &CompilationUnit,
+        provider: &mut dyn FileProvider,
+    ) -> Result<CrateAnalysis, KytheError> {
+        // The source files are needed to find the names of items within their spans
+        let mut offset_index = OffsetIndex::default();
+        for required_input in unit.get_required_input() {
+            let path = required_input.get_info().get_path();
+            if !path.ends_with(".rs") {
+                continue;
+            }
+            let file_bytes = provider.contents(path, required_input.get_info().get_digest())?;
+            let text = String::from_utf8(file_bytes).map_err(|_| {
+                KytheError::IndexerError(format!("Failed to read file {} as UTF8 string", path))
+            })?;
+            offset_index.add_file(&clean(path), &text);
+        }
+
+        // The arguments are only used to disambiguate the crates, so the crate can
+        // still be indexed if they aren't rustc arguments
+        let options = parse_arguments(unit.get_argument()).ok();
+        RustdocConverter::new(&self.krate, offset_index, options.as_ref())?.convert()
+    }
+}
+
+/// Returns whether a JSON file is rustdoc JSON rather than a save-analysis.
+/// Rustdoc JSON has the crate's `root` and its `format_version`, which
+/// the save-analysis doesn't have.
+pub fn is_rustdoc_json(json: &Value) -> bool {
+    json.as_object().map_or(false, |object| {
+        object.contains_key("format_version") || object.contains_key("root")
+    })
+}
+
+/// Converts the rustdoc JSON of a crate into the indexer's model
+struct RustdocConverter<'a> {
+    krate: &'a RustdocCrate,
+    offset_index: OffsetIndex,
+    analysis: CrateAnalysis,
+    next_index: u32,
+    // A map between the rustdoc Id of a definition and its Id
+    ids: HashMap<String, DefId>,
+    // A map between the rustdoc Id of a definition and its qualified name, used
+    // to write the types defined in the crate
+    qualnames: HashMap<String, String>,
+    impls: Vec<PendingImpl>,
+    // The traits of the crate and the rustdoc Ids of the traits they extend
+    supertraits: Vec<(DefId, String)>,
+}
+
+impl<'a> RustdocConverter<'a> {
+    fn new(
+        krate: &'a RustdocCrate,
+        offset_index: OffsetIndex,
+        options: Option<&CrateOptions>,
+    ) -> Result<Self, KytheError> {
+        let root = id_key(&krate.root).and_then(|key| krate.index.get(&key)).ok_or_else(|| {
+            KytheError::IndexerError("The rustdoc JSON did not contain the root module".to_string())
+        })?;
+        let crate_name = root.name.clone().unwrap_or_default();
+        let metadata = options.map(|options| options.metadata.clone()).unwrap_or_default();
+
+        // External crates are numbered by their position in the crate number order
+        let mut external_crates: Vec<(u32, &ExternalCrate)> = krate
+            .external_crates
+            .iter()
+            .filter_map(|(number, external_crate)| Some((number.parse().ok()?, external_crate)))
+            .collect();
+        external_crates.sort_by_key(|(number, _)| *number);
+        let external_crates = external_crates
+            .into_iter()
+            .map(|(_, external_crate)| {
+                let paths: Vec<String> = options
+                    .iter()
+                    .flat_map(|options| options.externs.iter())
+                    .filter(|(name, _)| *name == external_crate.name)
+                    .map(|(_, path)| path.clone())
+                    .collect();
+                CrateId {
+                    name: external_crate.name.clone(),
+                    disambiguator: generate_disambiguator(&external_crate.name, &paths),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            krate,
+            offset_index,
+            analysis: CrateAnalysis {
+                krate: CrateId {
+                    disambiguator: generate_disambiguator(&crate_name, &metadata),
+                    name: crate_name,
+                },
+                external_crates,
+                definitions: Vec::new(),
+                references: Vec::new(),
+                imports: Vec::new(),
+                implementations: Vec::new(),
+                relations: Vec::new(),
+                macro_references: Vec::new(),
+                canonical_paths: HashMap::new(),
+            },
+            next_index: 0,
+            ids: HashMap::new(),
+            qualnames: HashMap::new(),
+            impls: Vec::new(),
+            supertraits: Vec::new(),
+        })
+    }
+
+    /// Walks the crate's modules from its root and returns the analysis
+    fn convert(mut self) -> Result<CrateAnalysis, KytheError> {
+        let krate = self.krate;
+        let root_key = id_key(&krate.root).unwrap_or_default();
+        let root = &krate.index[&root_key];
+        self.add_module(&root_key, root, String::new(), None);
+
+        for pending_impl in std::mem::take(&mut self.impls) {
+            self.add_impl(&pending_impl);
+        }
+
+        // The CrateAnalyzer can only link the traits defined in the crate
+        for (trait_id, supertrait_key) in std::mem::take(&mut self.supertraits) {
+            if let Some(supertrait_id) = self.ids.get(&supertrait_key) {
+                self.analysis.relations.push(Relation {
+                    kind: RelationKind::SuperTrait,
+                    from: Some(*supertrait_id),
+                    to: Some(trait_id),
+                });
+            }
+        }
+        Ok(self.analysis)
+    }
+
+    /// Adds a module and its items. The `path` of the crate's root module is
+    /// empty and the `path` of other modules is their qualified name.
+    fn add_module(
+        &mut self,
+        key: &str,
+        item: &Item,
+        path: String,
+        parent_file: Option<&str>,
+    ) -> Option<DefId> {
+        let item_span = convert_span(item.span.as_ref()?);
+        let file_name = item_span.file_name.clone();
+
+        // The crate root and modules declared with `mod name;` are defined by their
+        // file, so they start at the top of it
+        let is_file_module = parent_file != Some(file_name.as_str());
+        let (span, value) = if is_file_module {
+            let span = Span {
+                file_name: file_name.clone(),
+                line_start: 1,
+                column_start: 1,
+                line_end: 1,
+                column_end: 1,
+            };
+            (span, file_name.clone())
+        } else {
+            (self.name_span(item)?, String::new())
+        };
+        let qualname = if path.is_empty() { "::".to_string() } else { path.clone() };
+        let mut def = self.new_def(key, item, DefKind::Mod, span, qualname);
+        def.value = value;
+
+        let (_, module) = tagged(&item.inner);
+        let mut impl_count = 0;
+        for child_key in array(&module["items"]).iter().filter_map(id_key) {
+            let child = match self.krate.index.get(&child_key) {
+                Some(child) if child.crate_id == 0 => child,
+                _ => continue,
+            };
+            let (kind, _) = tagged(&child.inner);
+            let child_id = match kind {
+                "module" => child.name.as_deref().and_then(|name| {
+                    let child_path = format!("{}::{}", path, name);
+                    self.add_module(&child_key, child, child_path, Some(&file_name))
+                }),
+                // Implementations are numbered in the order they appear in their module
+                "impl" => {
+                    self.impls.push(PendingImpl {
+                        key: child_key,
+                        module_path: path.clone(),
+                        number: impl_count,
+                    });
+                    impl_count += 1;
+                    None
+                }
+                _ => self.add_item(&child_key, child, &path),
+            };
+            def.children.extend(child_id);
+        }
+
+        let id = def.id;
+        self.push_def(def, &path);
+        Some(id)
+    }
+
+    /// Adds an item declared in the module at `path`, along with its fields,
+    /// variants, trait items, and generic parameters
+    fn add_item(&mut self, key: &str, item: &Item, path: &str) -> Option<DefId> {
+        let (kind, inner) = tagged(&item.inner);
+        let def_kind = match kind {
+            "struct" => DefKind::Struct,
+            "union" => DefKind::Union,
+            "enum" => DefKind::Enum,
+            "trait" => DefKind::Trait,
+            // Functions declared in `extern` blocks don't have a body
+            "function" if inner["has_body"] == Value::Bool(false) => DefKind::ForeignFunction,
+            "function" => DefKind::Function,
+            "type_alias" | "typedef" => DefKind::Type,
+            "constant" => DefKind::Const,
+            "static" => DefKind::Static,
+            "macro" => DefKind::Macro,
+            "extern_type" => DefKind::ExternType,
+            _ => return None,
+        };
+        let qualname = format!("{}::{}", path, item.name.as_deref()?);
+        let span = self.name_span(item)?;
+        let mut def = self.new_def(key, item, def_kind, span, qualname.clone());
+        def.value = match kind {
+            "function" => self.function_value(&def.name, inner),
+            "type_alias" | "typedef" | "constant" | "static" => self.type_string(&inner["type"]),
+            _ => String::new(),
+        };
+        if kind == "function" {
+            def.signature = Some(format!("{}{}", function_qualifiers(inner), def.value));
+        }
+
+        match kind {
+            "struct" | "union" => {
+                for field_key in field_keys(inner) {
+                    def.children.extend(self.add_field(&field_key, &qualname, def.id));
+                }
+            }
+            "enum" => {
+                for variant_key in array(&inner["variants"]).iter().filter_map(id_key) {
+                    def.children.extend(self.add_variant(&variant_key, &def.name, &qualname));
+                }
+            }
+            "trait" => {
+                for trait_item_key in array(&inner["items"]).iter().filter_map(id_key) {
+                    def.children.extend(self.add_assoc_item(
+                        &trait_item_key,
+                        &qualname,
+                        &qualname,
+                        Some(def.id),
+                    ));
+                }
+                for bound in array(&inner["bounds"]) {
+                    if let ("trait_bound", trait_bound) = tagged(bound) {
+                        if let Some(supertrait_key) = id_key(&trait_bound["trait"]["id"]) {
+                            self.supertraits.push((def.id, supertrait_key));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        self.add_generic_params(&def, &qualname, &inner["generics"]);
+        let id = def.id;
+        self.push_def(def, &qualname);
+        Some(id)
+    }
+
+    /// Adds a field of the struct, union, or variant `parent`
+    fn add_field(&mut self, key: &str, container_path: &str, parent: DefId) -> Option<DefId> {
+        let item = self.krate.index.get(key)?;
+        let qualname = format!("{}::{}", container_path, item.name.as_deref()?);
+        let span = self.name_span(item)?;
+        let mut def = self.new_def(key, item, DefKind::Field, span, qualname.clone());
+        let (_, field_type) = tagged(&item.inner);
+        def.value = self.type_string(field_type);
+        def.parent = Some(parent);
+        let id = def.id;
+        self.push_def(def, &qualname);
+        Some(id)
+    }
+
+    /// Adds a variant of the enum `enum_name` and its fields. The fields of
+    /// variants are linked to their variant by their parent.
+    fn add_variant(&mut self, key: &str, enum_name: &str, enum_path: &str) -> Option<DefId> {
+        let item = self.krate.index.get(key)?;
+        let qualname = format!("{}::{}", enum_path, item.name.as_deref()?);
+        let (_, variant) = tagged(&item.inner);
+        let (variant_kind, _) = tagged(&variant["kind"]);
+        let kind =
+            if variant_kind == "struct" { DefKind::StructVariant } else { DefKind::TupleVariant };
+        let span = self.name_span(item)?;
+        let mut def = self.new_def(key, item, kind, span, qualname.clone());
+
+        let mut field_values = Vec::new();
+        for field_key in field_keys(variant) {
+            let field = match self.krate.index.get(&field_key) {
+                Some(field) => field,
+                None => continue,
+            };
+            let field_type = self.type_string(tagged(&field.inner).1);
+            field_values.push(match (variant_kind, &field.name) {
+                ("struct", Some(name)) => format!("{}: {}", name, field_type),
+                _ => field_type,
+            });
+            self.add_field(&field_key, &qualname, def.id);
+        }
+        def.value = match variant_kind {
+            "struct" => format!("{}::{} {{ {} }}", enum_name, def.name, field_values.join(", ")),
+            "tuple" => format!("{}::{}({})", enum_name, def.name, field_values.join(", ")),
+            _ => format!("{}::{}", enum_name, def.name),
+        };
+        let id = def.id;
+        self.push_def(def, &qualname);
+        Some(id)
+    }
+
+    /// Adds a function, constant, or type declared in a trait or
+    /// implementation. Trait items are the children of their trait, which is
+    /// their `parent`.
+    fn add_assoc_item(
+        &mut self,
+        key: &str,
+        container: &str,
+        container_path: &str,
+        parent: Option<DefId>,
+    ) -> Option<DefId> {
+        let item = self.krate.index.get(key)?;
+        let (kind, inner) = tagged(&item.inner);
+        let def_kind = match kind {
+            "function" => DefKind::Method,
+            "assoc_const" => DefKind::Const,
+            "assoc_type" => DefKind::Type,
+            _ => return None,
+        };
+        let name = item.name.as_deref()?;
+        let qualname = format!("{}::{}", container, name);
+        let span = self.name_span(item)?;
+        let mut def = self.new_def(key, item, def_kind, span, qualname.clone());
+        def.value = match kind {
+            "function" => self.function_value(name, inner),
+            _ => self.type_string(&inner["type"]),
+        };
+        if kind == "function" {
+            def.signature = Some(format!("{}{}", function_qualifiers(inner), def.value));
+        }
+        def.parent = parent;
+
+        let def_path = format!("{}::{}", container_path, name);
+        self.add_generic_params(&def, &def_path, &inner["generics"]);
+        let id = def.id;
+        self.push_def(def, &def_path);
+        Some(id)
+    }
+
+    /// Adds an implementation, its items, and its relation to the type it is
+    /// implemented on. Synthetic implementations of auto traits and blanket
+    /// implementations are skipped.
+    fn add_impl(&mut self, pending_impl: &PendingImpl) {
+        let item = &self.krate.index[&pending_impl.key];
+        let (_, inner) = tagged(&item.inner);
+        if inner["is_synthetic"] == Value::Bool(true) || !inner["blanket_impl"].is_null() {
+            return;
+        }
+
+        let self_type = self.type_string(&inner["for"]);
+        let trait_path = &inner["trait"];
+        let container = if trait_path.is_null() {
+            format!("<{}>", self_type)
+        } else {
+            format!("<{} as {}>", self_type, self.path_string(trait_path))
+        };
+        let container_path =
+            format!("{}::{{impl#{}}}", pending_impl.module_path, pending_impl.number);
+
+        let impl_id = self.analysis.implementations.len() as u32;
+        let children = array(&inner["items"])
+            .iter()
+            .filter_map(id_key)
+            .filter_map(|key| self.add_assoc_item(&key, &container, &container_path, None))
+            .collect();
+        self.analysis.implementations.push(Implementation { id: impl_id, span: None, children });
+
+        // The CrateAnalyzer can only attribute methods to types defined in the crate
+        let (_, self_path) = tagged(&inner["for"]);
+        let self_id = id_key(&self_path["id"]).and_then(|key| self.ids.get(&key)).copied();
+        if let Some(self_id) = self_id {
+            let trait_id = id_key(&trait_path["id"]).and_then(|key| self.ids.get(&key)).copied();
+            self.analysis.relations.push(Relation {
+                kind: RelationKind::Impl { implementation: impl_id },
+                from: Some(self_id),
+                to: trait_id,
+            });
+        }
+    }
+
+    /// Adds the generic type parameters of a definition. Rustdoc doesn't
+    /// record the spans of generic parameters, so they are found in the source
+    /// after the definition's name.
+    fn add_generic_params(&mut self, def: &Definition, def_path: &str, generics: &Value) {
+        let file_name = &def.span.file_name;
+        let name_end =
+            self.offset_index.get_byte_offset(file_name, def.span.line_end, def.span.column_end);
+        let source_params = match (name_end, self.offset_index.get_file_contents(file_name)) {
+            (Some(name_end), Some(file_contents)) => find_generic_params(file_contents, name_end),
+            _ => return,
+        };
+
+        for param in array(&generics["params"]) {
+            let type_param = &param["kind"]["type"];
+            // Parameters for `impl Trait` arguments aren't written in the source
+            if type_param.is_null() || type_param["is_synthetic"] == Value::Bool(true) {
+                continue;
+            }
+            let name = param["name"].as_str().unwrap_or_default();
+            let source_param = source_params
+                .iter()
+                .find(|source_param| {
+                    source_param.kind == GenericParamKind::Type && source_param.name == name
+                })
+                .and_then(|source_param| self.span(file_name, &source_param.span));
+            let span = match source_param {
+                Some(span) => span,
+                None => continue,
+            };
+
+            let id = self.next_id();
+            self.analysis.canonical_paths.insert(id, format!("type{}::{}", def_path, name));
+            self.analysis.definitions.push(Definition {
+                kind: DefKind::Type,
+                id,
+                span,
+                name: name.to_string(),
+                qualname: format!("{}::{}${}", def.qualname, name, id.index),
+                value: String::new(),
+                parent: None,
+                children: Vec::new(),
+                declaration: None,
+                docs: String::new(),
+                signature: None,
+                attributes: Vec::new(),
+                visibility: None,
+            });
+        }
+    }
+
+    fn next_id(&mut self) -> DefId {
+        let id = DefId { krate: 0, index: self.next_index };
+        self.next_index += 1;
+        id
+    }
+
+    /// Creates the definition of an item. The caller fills in its value,
+    /// parent, and children before adding it with `push_def`.
+    fn new_def(
+        &mut self,
+        key: &str,
+        item: &Item,
+        kind: DefKind,
+        span: Span,
+        qualname: String,
+    ) -> Definition {
+        let id = self.next_id();
+        self.ids.insert(key.to_string(), id);
+        self.qualnames.insert(key.to_string(), qualname.clone());
+        Definition {
+            kind,
+            id,
+            span,
+            name: item.name.clone().unwrap_or_default(),
+            qualname,
+            value: String::new(),
+            parent: None,
+            children: Vec::new(),
+            declaration: None,
+            docs: item.docs.clone().unwrap_or_default(),
+            signature: None,
+            attributes: item.attrs.iter().map(convert_attribute).collect(),
+            visibility: convert_visibility(&item.visibility),
+        }
+    }
+
+    /// Adds a definition to the analysis along with its canonical path, which
+    /// is based on its path within the crate, `def_path`
+    fn push_def(&mut self, def: Definition, def_path: &str) {
+        let namespace = match def.kind {
+            DefKind::Macro => "macro",
+            DefKind::Enum
+            | DefKind::ExternType
+            | DefKind::Mod
+            | DefKind::Struct
+            | DefKind::StructVariant
+            | DefKind::Trait
+            | DefKind::TupleVariant
+            | DefKind::Type
+            | DefKind::Union => "type",
+            _ => "value",
+        };
+        self.analysis.canonical_paths.insert(def.id, format!("{}{}", namespace, def_path));
+        self.analysis.definitions.push(def);
+    }
+
+    /// Returns the span of an item's name, or the span of the entire item if
+    /// the name can't be found in the source, such as for tuple fields
+    fn name_span(&self, item: &Item) -> Option<Span> {
+        let item_span = convert_span(item.span.as_ref()?);
+        let name = item.name.as_deref().unwrap_or_default();
+        if !name.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            return Some(item_span);
+        }
+        let file_name = &item_span.file_name;
+        let name_span = self.byte_span(&item_span).and_then(|byte_span| {
+            let file_contents = self.offset_index.get_file_contents(file_name)?;
+            let name_span = find_item_name(file_contents, &byte_span, name)?;
+            self.span(file_name, &name_span)
+        });
+        Some(name_span.unwrap_or(item_span))
+    }
+
+    fn byte_span(&self, span: &Span) -> Option<ByteSpan> {
+        let file_name = &span.file_name;
+        Some(ByteSpan {
+            start_byte: self.offset_index.get_byte_offset(
+                file_name,
+                span.line_start,
+                span.column_start,
+            )?,
+            end_byte: self.offset_index.get_byte_offset(
+                file_name,
+                span.line_end,
+                span.column_end,
+            )?,
+        })
+    }
+
+    fn span(&self, file_name: &str, byte_span: &ByteSpan) -> Option<Span> {
+        let (line_start, column_start) =
+            self.offset_index.get_line_and_column(file_name, byte_span.start_byte)?;
+        let (line_end, column_end) =
+            self.offset_index.get_line_and_column(file_name, byte_span.end_byte)?;
+        Some(Span {
+            file_name: file_name.to_string(),
+            line_start,
+            column_start,
+            line_end,
+            column_end,
+        })
+    }
+
+    /// Returns the signature of a function, such as `fn add(lhs: u32, rhs:
+    /// u32) -> u32`
+    fn function_value(&self, name: &str, function: &Value) -> String {
+        // The signature was called the declaration in earlier versions of rustdoc
+        let signature = function.get("sig").unwrap_or(&function["decl"]);
+        let inputs: Vec<String> = array(&signature["inputs"])
+            .iter()
+            .map(|input| {
+                let name = input[0].as_str().unwrap_or_default();
+                format!("{}: {}", name, self.type_string(&input[1]))
+            })
+            .collect();
+        let output = match &signature["output"] {
+            Value::Null => String::new(),
+            output => format!(" -> {}", self.type_string(output)),
+        };
+        let generics = self.generics_string(&function["generics"]);
+        format!("fn {}{}({}){}", name, generics, inputs.join(", "), output)
+    }
+
+    /// Returns the generic parameters of an item as written in the source,
+    /// such as `<'a, T>`
+    fn generics_string(&self, generics: &Value) -> String {
+        let params: Vec<String> = array(&generics["params"])
+            .iter()
+            .filter_map(|param| {
+                let name = param["name"].as_str()?;
+                match tagged(&param["kind"]) {
+                    ("type", type_param) if type_param["is_synthetic"] == Value::Bool(true) => None,
+                    ("const", const_param) => {
+                        Some(format!("const {}: {}", name, self.type_string(&const_param["type"])))
+                    }
+                    _ => Some(name.to_string()),
+                }
+            })
+            .collect();
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", params.join(", "))
+        }
+    }
+
+    /// Returns the type in the format used by the save_analysis. Types defined
+    /// in the crate are written as their path within the crate, such as
+    /// `shapes::Square`.
+    fn type_string(&self, ty: &Value) -> String {
+        let (kind, value) = tagged(ty);
+        match kind {
+            "resolved_path" => self.path_string(value),
+            "generic" | "primitive" => value.as_str().unwrap_or_default().to_string(),
+            "borrowed_ref" => {
+                let lifetime = match value["lifetime"].as_str() {
+                    Some(lifetime) => format!("{} ", lifetime),
+                    None => String::new(),
+                };
+                let mutability = if value["is_mutable"] == Value::Bool(true) { "mut " } else { "" };
+                format!("&{}{}{}", lifetime, mutability, self.type_string(&value["type"]))
+            }
+            "raw_pointer" => {
+                let mutability =
+                    if value["is_mutable"] == Value::Bool(true) { "mut" } else { "const" };
+                format!("*{} {}", mutability, self.type_string(&value["type"]))
+            }
+            "slice" => format!("[{}]", self.type_string(value)),
+            "array" => format!(
+                "[{}; {}]",
+                self.type_string(&value["type"]),
+                value["len"].as_str().unwrap_or_default()
+            ),
+            "tuple" => {
+                let types: Vec<String> =
+                    array(value).iter().map(|ty| self.type_string(ty)).collect();
+                match types.len() {
+                    1 => format!("({},)", types[0]),
+                    _ => format!("({})", types.join(", ")),
+                }
+            }
+            "impl_trait" => format!("impl {}", self.bounds_string(value)),
+            "dyn_trait" => {
+                let traits: Vec<String> = array(&value["traits"])
+                    .iter()
+                    .map(|poly_trait| self.path_string(&poly_trait["trait"]))
+                    .collect();
+                format!("dyn {}", traits.join(" + "))
+            }
+            "qualified_path" => {
+                let self_type = self.type_string(&value["self_type"]);
+                let name = value["name"].as_str().unwrap_or_default();
+                match &value["trait"] {
+                    Value::Null => format!("{}::{}", self_type, name),
+                    trait_path => {
+                        format!("<{} as {}>::{}", self_type, self.path_string(trait_path), name)
+                    }
+                }
+            }
+            "function_pointer" => self.function_value("", value).replacen("fn (", "fn(", 1),
+            "infer" => "_".to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// Returns a path to a type or trait along with its generic arguments,
+    /// such as `Vec<u32>`
+    fn path_string(&self, path: &Value) -> String {
+        // The path was called the name in earlier versions of rustdoc
+        let written_path = path.get("path").unwrap_or(&path["name"]).as_str().unwrap_or_default();
+        let qualname = id_key(&path["id"]).and_then(|key| self.qualnames.get(&key));
+        let path_string = match qualname {
+            Some(qualname) => qualname.trim_start_matches("::"),
+            None => written_path,
+        };
+        let args = match tagged(&path["args"]) {
+            ("angle_bracketed", args) => {
+                let args: Vec<String> = array(&args["args"])
+                    .iter()
+                    .filter_map(|arg| match tagged(arg) {
+                        ("type", ty) => Some(self.type_string(ty)),
+                        ("lifetime", lifetime) => lifetime.as_str().map(str::to_string),
+                        ("const", constant) => constant["expr"].as_str().map(str::to_string),
+                        ("infer", _) => Some("_".to_string()),
+                        _ => None,
+                    })
+                    .collect();
+                if args.is_empty() {
+                    String::new()
+                } else {
+                    format!("<{}>", args.join(", "))
+                }
+            }
+            ("parenthesized", args) => {
+                let inputs: Vec<String> =
+                    array(&args["inputs"]).iter().map(|ty| self.type_string(ty)).collect();
+                let output = match &args["output"] {
+                    Value::Null => String::new(),
+                    output => format!(" -> {}", self.type_string(output)),
+                };
+                format!("({}){}", inputs.join(", "), output)
+            }
+            _ => String::new(),
+        };
+        format!("{}{}", path_string, args)
+    }
+
+    /// Returns the trait bounds of an `impl Trait` type, such as
+    /// `Display + 'a`
+    fn bounds_string(&self, bounds: &Value) -> String {
+        let bounds: Vec<String> = array(bounds)
+            .iter()
+            .filter_map(|bound| match tagged(bound) {
+                ("trait_bound", trait_bound) => Some(self.path_string(&trait_bound["trait"])),
+                ("outlives", lifetime) => lifetime.as_str().map(str::to_string),
+                _ => None,
+            })
+            .collect();
+        bounds.join(" + ")
+    }
+}
+
+/// Returns the variant and contents of an externally tagged enum in the
+/// rustdoc JSON, such as `("struct", {..})` for `{"struct": {..}}`. Variants
+/// without contents are written as strings.
+fn tagged(value: &Value) -> (&str, &Value) {
+    match value {
+        Value::String(variant) => (variant.as_str(), &NULL),
+        Value::Object(object) if object.len() == 1 => match object.iter().next() {
+            Some((variant, contents)) => (variant.as_str(), contents),
+            None => ("", &NULL),
+        },
+        _ => ("", &NULL),
+    }
+}
+
+/// Returns the elements of a JSON array, or an empty slice if the value isn't
+/// an array
+fn array(value: &Value) -> &[Value] {
+    value.as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+/// Returns the key of a rustdoc Id in the index. Ids are numbers in recent
+/// versions of rustdoc and strings in earlier ones.
+fn id_key(id: &Value) -> Option<String> {
+    match id {
+        Value::String(id) => Some(id.clone()),
+        Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+/// Returns the keys of the fields of a struct, union, or variant
+fn field_keys(inner: &Value) -> Vec<String> {
+    let fields = match tagged(&inner["kind"]) {
+        ("tuple", fields) => fields,
+        ("plain" | "struct", kind) => &kind["fields"],
+        // Unions and the structs of earlier versions of rustdoc list their fields
+        // directly
+        _ => &inner["fields"],
+    };
+    array(fields).iter().filter_map(id_key).collect()
+}
+
+fn convert_span(span: &ItemSpan) -> Span {
+    Span {
+        file_name: clean(&span.filename),
+        line_start: span.begin.0,
+        column_start: span.begin.1 + 1,
+        line_end: span.end.0,
+        column_end: span.end.1 + 1,
+    }
+}
+
+/// Converts the visibility of an item to the way it is written in the
+/// source. Items with the default visibility, such as private items and trait
+/// implementation items, don't have one.
+fn convert_visibility(visibility: &Value) -> Option<String> {
+    match tagged(visibility) {
+        ("public", _) => Some("pub".to_string()),
+        ("crate", _) => Some("pub(crate)".to_string()),
+        // The path of the module is relative to the crate, such as `::shapes`
+        ("restricted", restricted) => {
+            match restricted["path"].as_str().unwrap_or_default().trim_start_matches("::") {
+                "" | "crate" => Some("pub(crate)".to_string()),
+                path => Some(format!("pub(in crate::{})", path)),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the qualifiers of a function as they are written before its `fn`
+/// keyword, such as `const unsafe extern "C" `
+fn function_qualifiers(function: &Value) -> String {
+    let header = &function["header"];
+    let mut qualifiers = String::new();
+    for (field, qualifier) in [("const_", "const"), ("async_", "async"), ("unsafe_", "unsafe")] {
+        // The header was a list of the qualifiers in earlier versions of rustdoc
+        let is_listed = array(header).iter().any(|listed| listed.as_str() == Some(qualifier));
+        if header[field] == Value::Bool(true) || is_listed {
+            qualifiers.push_str(qualifier);
+            qualifiers.push(' ');
+        }
+    }
+
+    // The ABI was a string next to the header in earlier versions of rustdoc
+    let (abi, contents) = tagged(header.get("abi").unwrap_or(&function["abi"]));
+    let abi = match abi.trim_matches('"') {
+        "" | "Rust" => None,
+        "C" => Some("C".to_string()),
+        "Other" => contents.as_str().map(|abi| abi.trim_matches('"').to_string()),
+        abi => Some(abi.to_lowercase()),
+    };
+    if let Some(abi) = abi {
+        qualifiers.push_str(&format!("extern \"{}\" ", abi));
+    }
+    qualifiers
+}
+
+/// Converts an attribute to its text without the surrounding `#[]`, such as
+/// `repr(u8)`
+fn convert_attribute(attribute: &Value) -> String {
+    let text = match tagged(attribute) {
+        ("other", text) => text.as_str().unwrap_or_default(),
+        (text, _) => text,
+    };
+    let text = text.trim();
+    let inner_text = text.strip_prefix("#![").or_else(|| text.strip_prefix("#["));
+    inner_text.and_then(|text| text.strip_suffix(']')).unwrap_or(text).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_converter(krate: &RustdocCrate, source: &str) -> Result<CrateAnalysis, KytheError> {
+        let mut offset_index = OffsetIndex::default();
+        offset_index.add_file("src/lib.rs", source);
+        RustdocConverter::new(krate, offset_index, None)?.convert()
+    }
+
+    fn find_def<'a>(analysis: &'a CrateAnalysis, qualname: &str) -> &'a Definition {
+        analysis.definitions.iter().find(|def| def.qualname == qualname).unwrap()
+    }
+
+    #[test]
+    fn convert_works() {
+        let source =
+            "/// A shape\npub trait Shape {}\n\npub struct Square<T> {\n    side: T,\n}\n\n\
+            impl<T> Shape for Square<T> {}\n";
+        let json = r##"{
+            "root": 0,
+            "format_version": 39,
+            "external_crates": {"1": {"name": "std"}},
+            "index": {
+                "0": {"crate_id": 0, "name": "shapes", "visibility": "public",
+                      "span": {"filename": "src/lib.rs", "begin": [1, 0], "end": [8, 30]},
+                      "inner": {"module": {"is_crate": true, "items": [1, 2, 4]}}},
+                "1": {"crate_id": 0, "name": "Shape", "visibility": "public", "docs": "A shape",
+                      "span": {"filename": "src/lib.rs", "begin": [1, 0], "end": [2, 18]},
+                      "inner": {"trait": {"items": [], "bounds": [], "generics": {"params": []}}}},
+                "2": {"crate_id": 0, "name": "Square", "visibility": "public",
+                      "attrs": ["#[repr(C)]"],
+                      "span": {"filename": "src/lib.rs", "begin": [4, 0], "end": [6, 1]},
+                      "inner": {"struct": {"kind": {"plain": {"fields": [3]}},
+                                "generics": {"params": [{"name": "T",
+                                    "kind": {"type": {"bounds": [], "is_synthetic": false}}}]}}}},
+                "3": {"crate_id": 0, "name": "side", "visibility": "default",
+                      "span": {"filename": "src/lib.rs", "begin": [5, 4], "end": [5, 11]},
+                      "inner": {"struct_field": {"generic": "T"}}},
+                "4": {"crate_id": 0, "name": null, "visibility": "default",
+                      "span": {"filename": "src/lib.rs", "begin": [8, 0], "end": [8, 30]},
+                      "inner": {"impl": {"is_synthetic": false, "blanket_impl": null, "items": [],
+                                "trait": {"path": "Shape", "id": 1, "args": null},
+                                "for": {"resolved_path": {"path": "Square", "id": 2,
+                                    "args": {"angle_bracketed": {"args": [{"type": {"generic": "T"}}]}}}}}}}
+            }
+        }"##;
+        let krate: RustdocCrate = serde_json::from_str(json).unwrap();
+        let analysis = test_converter(&krate, source).unwrap();
+        assert_eq!(analysis.krate.name, "shapes");
+        assert_eq!(analysis.external_crates[0].name, "std");
+
+        let root = find_def(&analysis, "::");
+        assert_eq!(root.kind, DefKind::Mod);
+        assert_eq!(root.children.len(), 2);
+
+        let shape = find_def(&analysis, "::Shape");
+        assert_eq!(shape.docs, "A shape");
+        assert_eq!((shape.span.line_start, shape.span.column_start), (2, 11));
+        assert_eq!(shape.visibility.as_deref(), Some("pub"));
+        assert_eq!(analysis.canonical_paths[&shape.id], "type::Shape");
+
+        let square = find_def(&analysis, "::Square");
+        assert_eq!(square.attributes, vec!["repr(C)".to_string()]);
+        let side = find_def(&analysis, "::Square::side");
+        assert_eq!(side.value, "T");
+        assert_eq!(side.parent, Some(square.id));
+        assert_eq!(side.visibility, None);
+
+        let param = analysis.definitions.iter().find(|def| def.name == "T").unwrap();
+        assert!(param.qualname.starts_with("::Square::T$"));
+        assert_eq!((param.span.line_start, param.span.column_start), (4, 19));
+
+        let relation = &analysis.relations[0];
+        assert_eq!(relation.kind, RelationKind::Impl { implementation: 0 });
+        assert_eq!(relation.from, Some(square.id));
+        assert_eq!(relation.to, Some(shape.id));
+    }
+
+    #[test]
+    fn convert_visibility_works() {
+        let restricted: Value =
+            serde_json::from_str(r#"{"restricted": {"parent": 3, "path": "::shapes"}}"#).unwrap();
+        assert_eq!(convert_visibility(&restricted).as_deref(), Some("pub(in crate::shapes)"));
+        assert_eq!(convert_visibility(&Value::from("crate")).as_deref(), Some("pub(crate)"));
+        assert_eq!(convert_visibility(&Value::from("default")), None);
+    }
+
+    #[test]
+    fn function_qualifiers_works() {
+        let qualifiers = |json: &str| function_qualifiers(&serde_json::from_str(json).unwrap());
+        assert_eq!(
+            qualifiers(
+                r#"{"header": {"const_": true, "unsafe_": true, "async_": false, "abi": {"C": {"unwind": false}}}}"#
+            ),
+            "const unsafe extern \"C\" "
+        );
+        assert_eq!(qualifiers(r#"{"header": {"async_": true, "abi": "Rust"}}"#), "async ");
+        assert_eq!(
+            qualifiers(r#"{"header": ["unsafe"], "abi": "\"system\""}"#),
+            "unsafe extern \"system\" "
+        );
+    }
+
+    #[test]
+    fn is_rustdoc_json_works() {
+        let is_rustdoc = |json: &str| is_rustdoc_json(&serde_json::from_str(json).unwrap());
+        assert!(is_rustdoc(r#"{"root":"0:0","crate_version":null}"#));
+        assert!(is_rustdoc(r#"{"crate_version":null,"index":{},"format_version":16}"#));
+        assert!(!is_rustdoc(r#"{"config":{"output_file":null},"prelude":null}"#));
+        assert!(!is_rustdoc(r#"[{"root":0}]"#));
+    }
+}
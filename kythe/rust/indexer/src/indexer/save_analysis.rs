@@ -0,0 +1,296 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::KytheError;
+use crate::providers::FileProvider;
+
+use super::model::{
+    AnalysisSource, CrateAnalysis, CrateId, DefId, DefKind, Definition, Implementation, Import,
+    ImportKind, MacroReference, RefKind, Reference, Relation, RelationKind, Span,
+};
+
+use analysis_rust_proto::CompilationUnit;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// The suffix of the file written by the extractor alongside the
+/// save-analysis that contains the canonical paths of definitions
+pub const DEF_PATHS_SUFFIX: &str = ".def_paths.json";
+
+/// The value the save_analysis uses for the krate and index of an Id that
+/// doesn't refer to a definition
+const MAX_INT: u32 = 4294967295;
+
+/// The canonical path of a definition, as recorded by the extractor
+#[derive(Deserialize)]
+struct DefPathEntry {
+    /// The save-analysis Id of the definition
+    id: rls_data::Id,
+    /// The namespace of the definition: "type", "value", or "macro"
+    namespace: String,
+    /// The definition's path within its crate, including disambiguators
+    path: String,
+}
+
+/// Reads the save-analysis stored in a CompilationUnit by the extractor,
+/// along with the canonical paths of its definitions
+pub struct SaveAnalysisSource {
+    // Taken when the analysis is converted
+    analysis: Option<rls_data::Analysis>,
+}
+
+impl SaveAnalysisSource {
+    /// Create a new source from the parsed contents of a save-analysis file
+    pub fn new(json: Value) -> Result<Self, KytheError> {
+        Ok(Self { analysis: Some(serde_json::from_value(json)?) })
+    }
+}
+
+impl AnalysisSource for SaveAnalysisSource {
+    fn analyze(
+        &mut self,
+        unit: &CompilationUnit,
+        provider: &mut dyn FileProvider,
+    ) -> Result<CrateAnalysis, KytheError> {
+        let analysis = self.analysis.take().ok_or_else(|| {
+            KytheError::IndexerError("The save-analysis has already been analyzed".to_string())
+        })?;
+        let canonical_paths = get_def_paths(unit, provider)?;
+        convert_analysis(analysis, canonical_paths)
+    }
+}
+
+/// Reads the definition paths recorded by the extractor and returns a map
+/// between a definition's Id and its namespace and path, such as
+/// `type::shapes::Square`. Returns an empty map if the CompilationUnit
+/// doesn't contain definition paths.
+fn get_def_paths(
+    c_unit: &CompilationUnit,
+    provider: &mut dyn FileProvider,
+) -> Result<HashMap<DefId, String>, KytheError> {
+    let mut def_paths = HashMap::new();
+    for required_input in c_unit.get_required_input() {
+        let input_path = required_input.get_info().get_path();
+        if !input_path.ends_with(DEF_PATHS_SUFFIX) {
+            continue;
+        }
+        let hash = required_input.get_info().get_digest();
+        let file_bytes = provider.contents(input_path, hash)?;
+        let entries: Vec<DefPathEntry> = serde_json::from_slice(&file_bytes)?;
+        for entry in entries {
+            def_paths.insert(convert_id(entry.id), format!("{}{}", entry.namespace, entry.path));
+        }
+    }
+    Ok(def_paths)
+}
+
+/// Converts the save-analysis of a crate into the indexer's model
+pub fn convert_analysis(
+    analysis: rls_data::Analysis,
+    canonical_paths: HashMap<DefId, String>,
+) -> Result<CrateAnalysis, KytheError> {
+    let prelude = analysis
+        .prelude
+        .ok_or_else(|| KytheError::IndexerError("Crate did not have prelude data".to_string()))?;
+
+    Ok(CrateAnalysis {
+        krate: convert_crate_id(&prelude.crate_id),
+        external_crates: prelude
+            .external_crates
+            .iter()
+            .map(|external_krate| convert_crate_id(&external_krate.id))
+            .collect(),
+        definitions: analysis.defs.into_iter().map(convert_def).collect(),
+        references: analysis
+            .refs
+            .iter()
+            .map(|reference| Reference {
+                kind: match reference.kind {
+                    rls_data::RefKind::Function => RefKind::Function,
+                    rls_data::RefKind::Mod => RefKind::Mod,
+                    rls_data::RefKind::Type => RefKind::Type,
+                    rls_data::RefKind::Variable => RefKind::Variable,
+                },
+                span: convert_span(&reference.span),
+                target: convert_id(reference.ref_id),
+            })
+            .collect(),
+        imports: analysis
+            .imports
+            .iter()
+            .map(|import| Import {
+                kind: match import.kind {
+                    rls_data::ImportKind::Use => ImportKind::Use,
+                    rls_data::ImportKind::GlobUse => ImportKind::GlobUse,
+                    rls_data::ImportKind::ExternCrate => ImportKind::ExternCrate,
+                },
+                span: convert_span(&import.span),
+                target: import.ref_id.map(convert_id),
+                name: import.name.clone(),
+            })
+            .collect(),
+        implementations: analysis
+            .impls
+            .iter()
+            .map(|implementation| Implementation {
+                id: implementation.id,
+                span: Some(convert_span(&implementation.span)),
+                children: implementation.children.iter().copied().map(convert_id).collect(),
+            })
+            .collect(),
+        relations: analysis
+            .relations
+            .iter()
+            .map(|relation| Relation {
+                kind: match relation.kind {
+                    rls_data::RelationKind::Impl { id } => {
+                        RelationKind::Impl { implementation: id }
+                    }
+                    rls_data::RelationKind::SuperTrait => RelationKind::SuperTrait,
+                },
+                // The save_analysis uses maxint for the krate if a side of the relation
+                // isn't a definition, such as for inherent implementations
+                from: convert_relation_id(relation.from),
+                to: convert_relation_id(relation.to),
+            })
+            .collect(),
+        macro_references: analysis
+            .macro_refs
+            .iter()
+            .map(|macro_ref| MacroReference {
+                span: convert_span(&macro_ref.span),
+                callee_span: convert_span(&macro_ref.callee_span),
+            })
+            .collect(),
+        canonical_paths,
+    })
+}
+
+fn convert_crate_id(krate_id: &rls_data::GlobalCrateId) -> CrateId {
+    CrateId { name: krate_id.name.clone(), disambiguator: krate_id.disambiguator }
+}
+
+fn convert_id(id: rls_data::Id) -> DefId {
+    DefId { krate: id.krate, index: id.index }
+}
+
+fn convert_relation_id(id: rls_data::Id) -> Option<DefId> {
+    if id.krate == MAX_INT {
+        None
+    } else {
+        Some(convert_id(id))
+    }
+}
+
+fn convert_span(span: &rls_data::SpanData) -> Span {
+    Span {
+        file_name: span.file_name.to_string_lossy().to_string(),
+        line_start: span.line_start.0,
+        column_start: span.column_start.0,
+        line_end: span.line_end.0,
+        column_end: span.column_end.0,
+    }
+}
+
+fn convert_def(def: rls_data::Def) -> Definition {
+    Definition {
+        kind: match def.kind {
+            rls_data::DefKind::Const => DefKind::Const,
+            rls_data::DefKind::Enum => DefKind::Enum,
+            rls_data::DefKind::ExternType => DefKind::ExternType,
+            rls_data::DefKind::Field => DefKind::Field,
+            rls_data::DefKind::ForeignFunction => DefKind::ForeignFunction,
+            rls_data::DefKind::ForeignStatic => DefKind::ForeignStatic,
+            rls_data::DefKind::Function => DefKind::Function,
+            rls_data::DefKind::Local => DefKind::Local,
+            rls_data::DefKind::Macro => DefKind::Macro,
+            rls_data::DefKind::Method => DefKind::Method,
+            rls_data::DefKind::Mod => DefKind::Mod,
+            rls_data::DefKind::Static => DefKind::Static,
+            rls_data::DefKind::Struct => DefKind::Struct,
+            rls_data::DefKind::StructVariant => DefKind::StructVariant,
+            rls_data::DefKind::Trait => DefKind::Trait,
+            rls_data::DefKind::Tuple => DefKind::Tuple,
+            rls_data::DefKind::TupleVariant => DefKind::TupleVariant,
+            rls_data::DefKind::Type => DefKind::Type,
+            rls_data::DefKind::Union => DefKind::Union,
+        },
+        id: convert_id(def.id),
+        span: convert_span(&def.span),
+        name: def.name,
+        qualname: def.qualname,
+        value: def.value,
+        parent: def.parent.map(convert_id),
+        children: def.children.into_iter().map(convert_id).collect(),
+        declaration: def.decl_id.map(convert_id),
+        docs: def.docs,
+        signature: def.sig.map(|sig| sig.text),
+        attributes: def.attributes.into_iter().map(|attribute| attribute.value).collect(),
+        visibility: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rls_data::config::Config;
+    use rls_data::{Column, CratePreludeData, GlobalCrateId, Row, SpanData};
+    use std::path::PathBuf;
+
+    fn test_span() -> SpanData {
+        SpanData {
+            file_name: PathBuf::from("main.rs"),
+            byte_start: 0,
+            byte_end: 0,
+            line_start: Row::new_one_indexed(1),
+            line_end: Row::new_one_indexed(1),
+            column_start: Column::new_one_indexed(1),
+            column_end: Column::new_one_indexed(5),
+        }
+    }
+
+    #[test]
+    fn convert_analysis_requires_prelude() {
+        let analysis = rls_data::Analysis::new(Config::default());
+        assert!(convert_analysis(analysis, HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn convert_analysis_works() {
+        let mut analysis = rls_data::Analysis::new(Config::default());
+        analysis.prelude = Some(CratePreludeData {
+            crate_id: GlobalCrateId { name: "test_crate".to_string(), disambiguator: (1, 2) },
+            crate_root: String::new(),
+            external_crates: Vec::new(),
+            span: test_span(),
+        });
+        analysis.relations.push(rls_data::Relation {
+            span: test_span(),
+            kind: rls_data::RelationKind::Impl { id: 3 },
+            from: rls_data::Id { krate: 0, index: 7 },
+            to: rls_data::Id { krate: MAX_INT, index: MAX_INT },
+        });
+
+        let analysis = convert_analysis(analysis, HashMap::new()).unwrap();
+        assert_eq!(
+            analysis.krate,
+            CrateId { name: "test_crate".to_string(), disambiguator: (1, 2) }
+        );
+        let relation = &analysis.relations[0];
+        assert_eq!(relation.kind, RelationKind::Impl { implementation: 3 });
+        assert_eq!(relation.from, Some(DefId { krate: 0, index: 7 }));
+        assert_eq!(relation.to, None);
+    }
+}
@@ -0,0 +1,1705 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::indexer::analyzers::ByteSpan;
+
+/// The keywords that can come before an expression, which end the receiver of
+/// a method call when walking backward over it
+const EXPRESSION_KEYWORDS: [&str; 12] =
+    ["as", "break", "else", "if", "in", "let", "match", "move", "mut", "ref", "return", "while"];
+
+/// Keywords that may precede the identifier of an item
+const ITEM_KEYWORDS: [&str; 14] = [
+    "async", "auto", "const", "default", "enum", "extern", "fn", "mod", "pub", "static", "struct",
+    "trait", "union", "unsafe",
+];
+
+/// Finds the parameter list of the function whose name ends at `name_end` in
+/// `file_contents`, skipping over any generic parameters. Returns the byte
+/// span from the opening parenthesis to the closing parenthesis.
+pub fn find_parameter_list(file_contents: &str, name_end: u32) -> Option<ByteSpan> {
+    let bytes = file_contents.as_bytes();
+    let mut index = skip_whitespace(bytes, name_end as usize);
+
+    // Skip the generic parameters, if any
+    if bytes.get(index) == Some(&b'<') {
+        let mut depth = 0;
+        while index < bytes.len() {
+            if let Some(next) = skip_non_code(file_contents, index) {
+                index = next;
+                continue;
+            }
+            match bytes[index] {
+                b'<' => depth += 1,
+                // Ignore the arrow in `Fn() -> T` bounds
+                b'>' if index > 0 && bytes[index - 1] == b'-' => {}
+                b'>' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        index += 1;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+        index = skip_whitespace(bytes, index);
+    }
+
+    if bytes.get(index) != Some(&b'(') {
+        return None;
+    }
+    let start_byte = index;
+    let end_byte = find_closing_delimiter(file_contents, index)?;
+    Some(ByteSpan { start_byte: start_byte as u32, end_byte: end_byte as u32 })
+}
+
+/// Finds the parameters of the function whose name ends at `name_end` in
+/// `file_contents`. Returns the byte span of each parameter in the order they
+/// are declared, leaving out a `self` receiver, so that a parameter's index in
+/// the returned `Vec` is its position.
+pub fn find_parameters(file_contents: &str, name_end: u32) -> Option<Vec<ByteSpan>> {
+    let bytes = file_contents.as_bytes();
+    let list_span = find_parameter_list(file_contents, name_end)?;
+    let params = split_top_level(
+        file_contents,
+        list_span.start_byte as usize + 1,
+        list_span.end_byte as usize,
+        b',',
+    )
+    .into_iter()
+    // An empty list or a trailing comma leaves an empty part
+    .filter(|span| skip_whitespace(bytes, span.start_byte as usize) < span.end_byte as usize)
+    .filter(|span| !is_receiver(&file_contents[span.start_byte as usize..span.end_byte as usize]))
+    .collect();
+    Some(params)
+}
+
+/// Returns whether the parameter `param` is a method receiver, such as
+/// `&'a mut self` or `self: Box<Self>`
+fn is_receiver(param: &str) -> bool {
+    let mut rest = param.trim_start().trim_start_matches('&').trim_start();
+    if let Some(lifetime) = rest.strip_prefix('\'') {
+        rest = lifetime.trim_start_matches(|c: char| c.is_ascii_alphanumeric() || c == '_');
+        rest = rest.trim_start();
+    }
+    if let Some(after_mut) = rest.strip_prefix("mut") {
+        if after_mut.starts_with(|c: char| c.is_ascii_whitespace()) {
+            rest = after_mut.trim_start();
+        }
+    }
+    match rest.strip_prefix("self") {
+        Some(after_self) => !after_self.bytes().next().map_or(false, is_ident_byte),
+        None => false,
+    }
+}
+
+/// Finds the full span of the item whose identifier is at `ident_span`. The
+/// span starts at the first keyword of the item (such as `pub` or `fn`) and
+/// ends after the closing brace of the item's body, or after the terminating
+/// semicolon if the item has no body.
+pub fn find_item_span(file_contents: &str, ident_span: &ByteSpan) -> Option<ByteSpan> {
+    let start_byte = find_item_start(file_contents, ident_span.start_byte as usize);
+    let end_byte = find_item_end(file_contents, ident_span.end_byte as usize)?;
+    Some(ByteSpan { start_byte: start_byte as u32, end_byte: end_byte as u32 })
+}
+
+/// Finds the identifier `name` within the span of an item, such as the name
+/// of the function in `pub fn name() {}`. Attributes, comments, and literals
+/// are skipped. Returns `None` if the identifier isn't found.
+pub fn find_item_name(file_contents: &str, item_span: &ByteSpan, name: &str) -> Option<ByteSpan> {
+    let bytes = file_contents.as_bytes();
+    let end = (item_span.end_byte as usize).min(bytes.len());
+    let mut index = item_span.start_byte as usize;
+    while index < end {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        if bytes[index] == b'#' {
+            let open = skip_whitespace(bytes, index + 1);
+            let open = if bytes.get(open) == Some(&b'!') { open + 1 } else { open };
+            if let Some(close) = find_closing_delimiter(file_contents, open) {
+                index = close + 1;
+                continue;
+            }
+        }
+        if !is_ident_byte(bytes[index]) {
+            index += 1;
+            continue;
+        }
+        let (mut word_end, mut word) = next_word(bytes, index)?;
+        // Raw identifiers such as `r#type`
+        if word == "r" && bytes.get(word_end) == Some(&b'#') {
+            if let Some((raw_end, raw_word)) = next_word(bytes, word_end + 1) {
+                word_end = raw_end;
+                word = raw_word;
+            }
+        }
+        if word == name && word_end <= end {
+            return Some(ByteSpan { start_byte: index as u32, end_byte: word_end as u32 });
+        }
+        index = word_end;
+    }
+    None
+}
+
+/// The kinds of generic parameters that can be declared on an item
+#[derive(Debug, PartialEq)]
+pub enum GenericParamKind {
+    Const,
+    Lifetime,
+    Type,
+}
+
+/// A generic parameter declared on an item
+pub struct GenericParam {
+    pub kind: GenericParamKind,
+    pub name: String,
+    // The span of the parameter's name
+    pub span: ByteSpan,
+}
+
+/// Finds the generic parameters declared on the item whose name ends at
+/// `name_end`, in the order they are declared. Returns an empty `Vec` if the
+/// item isn't generic.
+pub fn find_generic_params(file_contents: &str, name_end: u32) -> Vec<GenericParam> {
+    let bytes = file_contents.as_bytes();
+    let mut params = Vec::new();
+    let open = skip_whitespace(bytes, name_end as usize);
+    if bytes.get(open) != Some(&b'<') {
+        return params;
+    }
+
+    // Split the parameters on the commas that aren't nested inside of bounds or
+    // default values
+    let mut angle_depth = 0;
+    let mut delimiter_depth = 0;
+    let mut param_start = open + 1;
+    let mut index = open;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        match bytes[index] {
+            b'<' => angle_depth += 1,
+            // Ignore the arrow in `Fn() -> T` bounds
+            b'>' if index > 0 && bytes[index - 1] == b'-' => {}
+            b'>' => {
+                angle_depth -= 1;
+                if angle_depth == 0 {
+                    params.extend(parse_generic_param(file_contents, param_start, index));
+                    break;
+                }
+            }
+            b'(' | b'[' | b'{' => delimiter_depth += 1,
+            b')' | b']' | b'}' => delimiter_depth -= 1,
+            b',' if angle_depth == 1 && delimiter_depth == 0 => {
+                params.extend(parse_generic_param(file_contents, param_start, index));
+                param_start = index + 1;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    params
+}
+
+/// Parses the generic parameter declared between `start` and `end`
+fn parse_generic_param(file_contents: &str, start: usize, end: usize) -> Option<GenericParam> {
+    let bytes = file_contents.as_bytes();
+    let mut index = skip_whitespace(bytes, start);
+    if index >= end {
+        return None;
+    }
+    let kind = if bytes[index] == b'\'' {
+        index += 1;
+        GenericParamKind::Lifetime
+    } else if file_contents[index..end].starts_with("const")
+        && matches!(bytes.get(index + 5), Some(byte) if byte.is_ascii_whitespace())
+    {
+        index = skip_whitespace(bytes, index + 5);
+        GenericParamKind::Const
+    } else {
+        GenericParamKind::Type
+    };
+
+    let name_start = index;
+    while index < end && is_ident_byte(bytes[index]) {
+        index += 1;
+    }
+    if name_start == index {
+        return None;
+    }
+    Some(GenericParam {
+        kind,
+        name: file_contents[name_start..index].to_string(),
+        span: ByteSpan { start_byte: name_start as u32, end_byte: index as u32 },
+    })
+}
+
+/// Walks backward from the name of the type being implemented on, which
+/// starts at `self_type_start`, to the `impl` keyword of the implementation
+/// and returns its span. Returns `None` if the keyword isn't found.
+pub fn find_impl_keyword(file_contents: &str, self_type_start: u32) -> Option<ByteSpan> {
+    let bytes = file_contents.as_bytes();
+    let mut depth = 0;
+    let mut index = self_type_start as usize;
+    while index > 0 {
+        index -= 1;
+        match bytes[index] {
+            // Ignore the arrow in `Fn() -> T` bounds
+            b'>' if index > 0 && bytes[index - 1] == b'-' => index -= 1,
+            b'>' | b')' | b']' | b'}' => depth += 1,
+            b'<' | b'(' | b'[' | b'{' if depth == 0 => return None,
+            b'<' | b'(' | b'[' | b'{' => depth -= 1,
+            b';' => return None,
+            byte if is_ident_byte(byte) => {
+                let (word_start, word) = previous_word(bytes, index + 1)?;
+                if depth == 0 && word == "impl" {
+                    return Some(ByteSpan {
+                        start_byte: word_start as u32,
+                        end_byte: (index + 1) as u32,
+                    });
+                }
+                index = word_start;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Finds the uses of the generic parameter `name`, declared at `decl_span`,
+/// from the end of its declaration to the end of the item that declares it
+pub fn find_generic_param_uses(
+    file_contents: &str,
+    name: &str,
+    decl_span: &ByteSpan,
+) -> Vec<ByteSpan> {
+    let bytes = file_contents.as_bytes();
+    let start = decl_span.end_byte as usize;
+    let end = match find_item_end(file_contents, start) {
+        Some(end) => end,
+        None => return Vec::new(),
+    };
+
+    let mut uses = Vec::new();
+    let mut index = start;
+    while index < end {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        if !is_ident_byte(bytes[index]) {
+            index += 1;
+            continue;
+        }
+
+        // Read the whole identifier
+        let word_start = index;
+        while index < end && is_ident_byte(bytes[index]) {
+            index += 1;
+        }
+        if &file_contents[word_start..index] != name {
+            continue;
+        }
+
+        // Ignore identifiers that are path segments, fields, or lifetimes
+        let mut previous = word_start;
+        while previous > 0 && bytes[previous - 1].is_ascii_whitespace() {
+            previous -= 1;
+        }
+        let is_member = previous > 0 && matches!(bytes[previous - 1], b'.' | b'\'')
+            || (previous > 1 && &bytes[previous - 2..previous] == b"::");
+        if !is_member {
+            uses.push(ByteSpan { start_byte: word_start as u32, end_byte: index as u32 });
+        }
+    }
+    uses
+}
+
+/// A `macro_rules!` macro defined in a file
+pub struct MacroDefinition {
+    // The span of the macro's name
+    pub name_span: ByteSpan,
+    // The span of the entire definition, from `macro_rules` to the end of its body
+    pub item_span: ByteSpan,
+}
+
+/// Finds the `macro_rules!` macros defined in the file
+pub fn find_macro_definitions(file_contents: &str) -> Vec<MacroDefinition> {
+    let bytes = file_contents.as_bytes();
+    let mut definitions = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        if !is_ident_byte(bytes[index]) {
+            index += 1;
+            continue;
+        }
+
+        // Read the whole identifier
+        let item_start = index;
+        while index < bytes.len() && is_ident_byte(bytes[index]) {
+            index += 1;
+        }
+        if &file_contents[item_start..index] != "macro_rules" {
+            continue;
+        }
+        let bang = skip_whitespace(bytes, index);
+        if bytes.get(bang) != Some(&b'!') {
+            continue;
+        }
+
+        let name_start = skip_whitespace(bytes, bang + 1);
+        let mut name_end = name_start;
+        while name_end < bytes.len() && is_ident_byte(bytes[name_end]) {
+            name_end += 1;
+        }
+        if name_start == name_end {
+            continue;
+        }
+
+        let open = skip_whitespace(bytes, name_end);
+        let close = match find_closing_delimiter(file_contents, open) {
+            Some(close) => close,
+            None => continue,
+        };
+        // Macros defined with parentheses or brackets end with a semicolon
+        let mut item_end = close + 1;
+        let semicolon = skip_whitespace(bytes, item_end);
+        if bytes[open] != b'{' && bytes.get(semicolon) == Some(&b';') {
+            item_end = semicolon + 1;
+        }
+
+        definitions.push(MacroDefinition {
+            name_span: ByteSpan { start_byte: name_start as u32, end_byte: name_end as u32 },
+            item_span: ByteSpan { start_byte: item_start as u32, end_byte: item_end as u32 },
+        });
+        index = item_end;
+    }
+    definitions
+}
+
+/// Given the start of a macro invocation such as `std::println!(...)`,
+/// returns the span of the macro's name
+pub fn find_macro_name(file_contents: &str, call_start: u32) -> Option<ByteSpan> {
+    let bytes = file_contents.as_bytes();
+    let mut index = call_start as usize;
+    let mut name_start = index;
+    while index < bytes.len() {
+        match bytes[index] {
+            byte if is_ident_byte(byte) => {}
+            // The name is the last segment of the path
+            b':' => name_start = index + 1,
+            b'!' if name_start < index => {
+                return Some(ByteSpan { start_byte: name_start as u32, end_byte: index as u32 });
+            }
+            _ => return None,
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Finds the call expression whose callee's name is at `name_span` and
+/// returns its span, such as `Shape::area(&square)` or `square.area()` for
+/// `area`. The call starts at the callee's path, or at the receiver of a method
+/// call, and ends after the argument list, including calls with a turbofish
+/// such as `parse::<u32>()`. Returns `None` if the name isn't called, as in
+/// `let f = area;` or `area as fn(&Square) -> u32`.
+pub fn find_call_span(file_contents: &str, name_span: &ByteSpan) -> Option<ByteSpan> {
+    let bytes = file_contents.as_bytes();
+    let mut index = skip_whitespace(bytes, name_span.end_byte as usize);
+    if file_contents[index..].starts_with("::") {
+        index = skip_whitespace(bytes, index + 2);
+        if bytes.get(index) != Some(&b'<') {
+            return None;
+        }
+        let mut depth = 0;
+        while index < bytes.len() {
+            match bytes[index] {
+                b'<' => depth += 1,
+                // Ignore the arrow in `Fn() -> T` arguments
+                b'>' if bytes[index - 1] == b'-' => {}
+                b'>' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+        index = skip_whitespace(bytes, index + 1);
+    }
+    if bytes.get(index) != Some(&b'(') {
+        return None;
+    }
+    let close = find_closing_delimiter(file_contents, index)?;
+
+    // Walk backward over the segments of the callee's path and the receivers of
+    // method calls, such as `Shape::` or `self.shapes[0].`
+    let mut start = name_span.start_byte as usize;
+    loop {
+        let previous = skip_whitespace_backward(bytes, start);
+        let operand_end = if bytes[..previous].ends_with(b"::") {
+            previous - 2
+        } else if bytes[..previous].ends_with(b".") && !bytes[..previous].ends_with(b"..") {
+            previous - 1
+        } else {
+            break;
+        };
+        match find_operand_start(bytes, operand_end) {
+            Some(operand_start) => start = operand_start,
+            None => break,
+        }
+    }
+    Some(ByteSpan { start_byte: start as u32, end_byte: (close + 1) as u32 })
+}
+
+/// Walks backward over the path segment or receiver that ends at `end`, such
+/// as `Shape`, `<T as Shape>`, `shapes[0]`, or `get()?`, and returns its start.
+/// Returns `None` if there isn't one.
+fn find_operand_start(bytes: &[u8], end: usize) -> Option<usize> {
+    let mut index = skip_whitespace_backward(bytes, end);
+    let mut start = None;
+    while index > 0 {
+        match bytes[index - 1] {
+            b'?' => index -= 1,
+            // Ignore the arrow in `Fn() -> T` arguments
+            b'>' if index > 1 && bytes[index - 2] == b'-' => break,
+            close @ (b')' | b']' | b'}' | b'>') => {
+                let open = match close {
+                    b')' => b'(',
+                    b']' => b'[',
+                    b'}' => b'{',
+                    _ => b'<',
+                };
+                index = find_opening_delimiter(bytes, index - 1, open, close)?;
+                start = Some(index);
+            }
+            b'"' => {
+                let open = bytes[..index - 1]
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .find(|(open, byte)| **byte == b'"' && (*open == 0 || bytes[open - 1] != b'\\'))
+                    .map(|(open, _)| open)?;
+                return Some(open);
+            }
+            byte if is_ident_byte(byte) => {
+                let (word_start, word) = previous_word(bytes, index)?;
+                if EXPRESSION_KEYWORDS.contains(&word) {
+                    break;
+                }
+                return Some(word_start);
+            }
+            _ => break,
+        }
+    }
+    start
+}
+
+/// Given the offset of a closing delimiter, returns the offset of the matching
+/// opening delimiter
+fn find_opening_delimiter(
+    bytes: &[u8],
+    close: usize,
+    open_byte: u8,
+    close_byte: u8,
+) -> Option<usize> {
+    let mut depth = 0;
+    let mut index = close + 1;
+    while index > 0 {
+        index -= 1;
+        if bytes[index] == close_byte {
+            // Ignore the arrow in `Fn() -> T` arguments
+            if close_byte == b'>' && index > 0 && bytes[index - 1] == b'-' {
+                continue;
+            }
+            depth += 1;
+        } else if bytes[index] == open_byte {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+/// Returns whether the local variable whose name is at `name_span` is
+/// declared by a `let` statement without an initializer, such as
+/// `let x: u32;` or `let (x, y): (u32, u32);`
+pub fn is_uninitialized_local(file_contents: &str, name_span: &ByteSpan) -> bool {
+    let bytes = file_contents.as_bytes();
+    let let_end = match find_let_keyword(bytes, name_span.start_byte as usize) {
+        Some(let_end) => let_end,
+        None => return false,
+    };
+
+    // Look for an initializer before the end of the statement
+    let mut depth = 0;
+    let mut index = let_end;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        match bytes[index] {
+            b'(' | b'[' | b'{' | b'<' => depth += 1,
+            // Ignore the arrow in `fn() -> T` types
+            b'>' if bytes[index - 1] == b'-' => {}
+            b')' | b']' | b'}' | b'>' => depth -= 1,
+            // Equals signs can appear in types such as `impl Iterator<Item = u8>`
+            b'=' if depth == 0 => return false,
+            b';' if depth == 0 => return true,
+            _ => {}
+        }
+        index += 1;
+    }
+    false
+}
+
+/// A variant declared in the body of an enum
+pub struct EnumVariant {
+    pub name_span: ByteSpan,
+    // The expression of the variant's explicit discriminant, if any
+    pub discriminant: Option<String>,
+}
+
+/// Finds the variants of the enum whose name ends at `name_end`, in the order
+/// they are declared
+pub fn find_enum_variants(file_contents: &str, name_end: u32) -> Vec<EnumVariant> {
+    let bytes = file_contents.as_bytes();
+    let mut variants = Vec::new();
+
+    // Find the enum's body, skipping over any generics and where clauses
+    let mut index = name_end as usize;
+    while index < bytes.len() && bytes[index] != b'{' {
+        match skip_non_code(file_contents, index) {
+            Some(next) => index = next,
+            None => index += 1,
+        }
+    }
+    let close = match find_closing_delimiter(file_contents, index) {
+        Some(close) => close,
+        None => return variants,
+    };
+
+    index += 1;
+    while index < close {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        // Skip over attributes such as `#[default]`
+        if bytes[index] == b'#' {
+            let open = skip_whitespace(bytes, index + 1);
+            index = match find_closing_delimiter(file_contents, open) {
+                Some(attribute_end) => attribute_end + 1,
+                None => open + 1,
+            };
+            continue;
+        }
+        if !is_ident_byte(bytes[index]) {
+            index += 1;
+            continue;
+        }
+
+        let name_start = index;
+        while index < close && is_ident_byte(bytes[index]) {
+            index += 1;
+        }
+        let name_span = ByteSpan { start_byte: name_start as u32, end_byte: index as u32 };
+
+        // Skip over the fields of tuple and struct variants
+        index = skip_whitespace(bytes, index);
+        if bytes[index] == b'(' || bytes[index] == b'{' {
+            index = match find_closing_delimiter(file_contents, index) {
+                Some(fields_end) => skip_whitespace(bytes, fields_end + 1),
+                None => close,
+            };
+        }
+
+        // The discriminant ends at the comma that separates the variants
+        let mut discriminant = None;
+        if bytes[index] == b'=' {
+            let expression_start = index + 1;
+            let mut depth = 0;
+            while index < close {
+                if let Some(next) = skip_non_code(file_contents, index) {
+                    index = next;
+                    continue;
+                }
+                match bytes[index] {
+                    b'(' | b'[' | b'{' => depth += 1,
+                    b')' | b']' | b'}' => depth -= 1,
+                    b',' if depth == 0 => break,
+                    _ => {}
+                }
+                index += 1;
+            }
+            discriminant = Some(file_contents[expression_start..index].trim().to_string());
+        }
+        variants.push(EnumVariant { name_span, discriminant });
+    }
+    variants
+}
+
+/// Parses an integer literal such as `0x1F`, `-3` or `1_000u32`. Returns
+/// `None` if the text isn't an integer literal.
+pub fn parse_integer_literal(text: &str) -> Option<i128> {
+    let text = text.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text.trim_start()),
+        None => (false, text),
+    };
+    let (radix, digits) = match text.get(..2) {
+        Some("0x") => (16, &text[2..]),
+        Some("0o") => (8, &text[2..]),
+        Some("0b") => (2, &text[2..]),
+        _ => (10, text),
+    };
+
+    // Remove the type suffix and separators
+    let digits = match digits.find(['i', 'u']) {
+        Some(suffix_start) => &digits[..suffix_start],
+        None => digits,
+    };
+    let digits = digits.replace('_', "");
+    if digits.is_empty() {
+        return None;
+    }
+    let value = i128::from_str_radix(&digits, radix).ok()?;
+    Some(if negative { -value } else { value })
+}
+
+/// The syntax of a type written in the source code
+pub enum TypeSyntax {
+    // A path such as `std::result::Result<T, E>`
+    Path {
+        // The last segment of the path
+        name: String,
+        name_span: ByteSpan,
+        args: Vec<TypeSyntax>,
+    },
+    // A reference such as `&'a mut T`
+    Reference(Box<TypeSyntax>),
+    // A slice such as `[T]`
+    Slice(Box<TypeSyntax>),
+    // An array such as `[T; 4]`
+    Array(Box<TypeSyntax>),
+    // A tuple such as `(A, B)`
+    Tuple(Vec<TypeSyntax>),
+    // Any type that isn't modeled, such as function pointers and trait objects
+    Other,
+}
+
+/// Finds the span of the aliased type of the type alias whose name ends at
+/// `name_end`
+pub fn find_type_alias_value(file_contents: &str, name_end: u32) -> Option<ByteSpan> {
+    let bytes = file_contents.as_bytes();
+
+    // Skip over the alias' generic parameters, which may have default values
+    let mut angle_depth = 0;
+    let mut index = name_end as usize;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        match bytes[index] {
+            b'<' => angle_depth += 1,
+            b'>' if bytes[index - 1] == b'-' => {}
+            b'>' => angle_depth -= 1,
+            b'=' if angle_depth == 0 => break,
+            b';' => return None,
+            _ => {}
+        }
+        index += 1;
+    }
+
+    let value_start = index + 1;
+    let end = split_top_level(file_contents, value_start, bytes.len(), b';').into_iter().next()?;
+    Some(ByteSpan { start_byte: value_start as u32, end_byte: end.end_byte })
+}
+
+/// Parses the type written between the offsets of `span`
+pub fn parse_type(file_contents: &str, span: &ByteSpan) -> TypeSyntax {
+    let bytes = file_contents.as_bytes();
+    let mut start = skip_whitespace(bytes, span.start_byte as usize);
+    let mut end = span.end_byte as usize;
+    while end > start && bytes[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    if start >= end {
+        return TypeSyntax::Other;
+    }
+
+    match bytes[start] {
+        b'&' => {
+            // Skip over the lifetime and mutability of the reference
+            start = skip_whitespace(bytes, start + 1);
+            if bytes[start] == b'\'' {
+                start += 1;
+                while start < end && is_ident_byte(bytes[start]) {
+                    start += 1;
+                }
+            }
+            if let Some((word_end, "mut")) = next_word(bytes, start) {
+                start = word_end;
+            }
+            let inner = ByteSpan { start_byte: start as u32, end_byte: end as u32 };
+            TypeSyntax::Reference(Box::new(parse_type(file_contents, &inner)))
+        }
+        b'(' | b'[' => {
+            if find_closing_delimiter(file_contents, start) != Some(end - 1) {
+                return TypeSyntax::Other;
+            }
+            if bytes[start] == b'[' {
+                let parts = split_top_level(file_contents, start + 1, end - 1, b';');
+                let element = Box::new(parse_type(file_contents, &parts[0]));
+                return if parts.len() > 1 {
+                    TypeSyntax::Array(element)
+                } else {
+                    TypeSyntax::Slice(element)
+                };
+            }
+
+            // The last part is empty for the unit type and tuples with a trailing comma
+            let mut parts = split_top_level(file_contents, start + 1, end - 1, b',');
+            let last_part = &parts[parts.len() - 1];
+            if file_contents[last_part.start_byte as usize..last_part.end_byte as usize]
+                .trim()
+                .is_empty()
+            {
+                parts.pop();
+            } else if parts.len() == 1 {
+                // A parenthesized type such as `(T)` isn't a tuple
+                return parse_type(file_contents, &parts[0]);
+            }
+            TypeSyntax::Tuple(parts.iter().map(|part| parse_type(file_contents, part)).collect())
+        }
+        _ => {
+            // Read the path, keeping track of its last segment
+            let mut index = start;
+            let mut name_start = start;
+            while index < end && (is_ident_byte(bytes[index]) || bytes[index] == b':') {
+                if bytes[index] == b':' {
+                    name_start = index + 1;
+                }
+                index += 1;
+            }
+            if name_start == index || !is_ident_byte(bytes[name_start]) {
+                return TypeSyntax::Other;
+            }
+            let name_span = ByteSpan { start_byte: name_start as u32, end_byte: index as u32 };
+            let name = file_contents[name_start..index].to_string();
+            if matches!(name.as_str(), "dyn" | "impl" | "fn" | "unsafe" | "extern") {
+                return TypeSyntax::Other;
+            }
+
+            // Parse the generic arguments
+            let mut args = Vec::new();
+            let open = skip_whitespace(bytes, index);
+            if open < end {
+                if bytes[open] != b'<' || bytes[end - 1] != b'>' {
+                    return TypeSyntax::Other;
+                }
+                for part in split_top_level(file_contents, open + 1, end - 1, b',') {
+                    let text =
+                        file_contents[part.start_byte as usize..part.end_byte as usize].trim();
+                    // Lifetimes aren't modeled
+                    if text.starts_with('\'') {
+                        continue;
+                    }
+                    args.push(parse_type(file_contents, &part));
+                }
+            }
+            TypeSyntax::Path { name, name_span, args }
+        }
+    }
+}
+
+/// A `mod name;` declaration of a module whose contents are in another file
+pub struct ModuleDeclaration {
+    pub name: String,
+    pub name_span: ByteSpan,
+    /// The value of the declaration's `#[path]` attribute, if any
+    pub path: Option<String>,
+    /// The names of the inline modules containing the declaration, from the
+    /// outermost module to the innermost
+    pub inline_modules: Vec<String>,
+}
+
+/// Finds the declarations of out-of-line modules in the file
+pub fn find_module_declarations(file_contents: &str) -> Vec<ModuleDeclaration> {
+    let bytes = file_contents.as_bytes();
+    let mut declarations = Vec::new();
+    // The names of the inline modules containing the current position and the
+    // brace depth of their bodies
+    let mut inline_modules: Vec<(String, usize)> = Vec::new();
+    let mut depth = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        match bytes[index] {
+            b'{' => depth += 1,
+            b'}' => {
+                if matches!(inline_modules.last(), Some((_, module_depth)) if *module_depth == depth)
+                {
+                    inline_modules.pop();
+                }
+                depth = depth.saturating_sub(1);
+            }
+            byte if is_ident_byte(byte) => {
+                let word_start = index;
+                while index < bytes.len() && is_ident_byte(bytes[index]) {
+                    index += 1;
+                }
+                if &file_contents[word_start..index] != "mod" {
+                    continue;
+                }
+                let (name_end, name) = match next_word(bytes, index) {
+                    Some(word) => word,
+                    None => continue,
+                };
+                let name_span = ByteSpan {
+                    start_byte: (name_end - name.len()) as u32,
+                    end_byte: name_end as u32,
+                };
+                let next = skip_whitespace(bytes, name_end);
+                match bytes.get(next) {
+                    Some(b';') => {
+                        let path = find_outer_attributes(file_contents, &name_span)
+                            .iter()
+                            .find_map(|attribute| match parse_attribute(attribute) {
+                                ("path", path) => path,
+                                _ => None,
+                            });
+                        declarations.push(ModuleDeclaration {
+                            name: name.to_string(),
+                            name_span,
+                            path,
+                            inline_modules: inline_modules
+                                .iter()
+                                .map(|(module_name, _)| module_name.clone())
+                                .collect(),
+                        });
+                    }
+                    Some(b'{') => {
+                        depth += 1;
+                        inline_modules.push((name.to_string(), depth));
+                    }
+                    _ => {
+                        index = name_end;
+                        continue;
+                    }
+                }
+                index = next;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    declarations
+}
+
+/// Finds the visibility written before the keyword of the item whose
+/// identifier is at `ident_span`, such as `pub` or `pub(crate)`. Returns
+/// `None` if the item is private.
+pub fn find_item_visibility(file_contents: &str, ident_span: &ByteSpan) -> Option<String> {
+    let start = find_item_start(file_contents, ident_span.start_byte as usize);
+    let header = file_contents[start..ident_span.start_byte as usize].trim_start();
+    let rest = header.strip_prefix("pub")?.trim_start();
+    match rest.strip_prefix('(') {
+        Some(restriction) => {
+            let path: Vec<&str> =
+                restriction[..restriction.find(')')?].split_whitespace().collect();
+            // `pub(self)` is the same as private
+            (path != ["self"]).then(|| format!("pub({})", path.join(" ")))
+        }
+        None => Some("pub".to_string()),
+    }
+}
+
+/// Finds the outer attributes of the item whose identifier is at
+/// `ident_span` and returns their contents, such as `test` for `#[test]`.
+/// Comments between the attributes are skipped. Attributes that span multiple
+/// lines aren't supported, so the attributes provided by the analysis are
+/// preferred; this is only needed for the ones removed by the compiler.
+pub fn find_outer_attributes(file_contents: &str, ident_span: &ByteSpan) -> Vec<String> {
+    let mut attributes = Vec::new();
+    // The first line only contains the text before the item's keywords
+    let mut line_end = find_item_start(file_contents, ident_span.start_byte as usize);
+    loop {
+        let line_start = file_contents[..line_end].rfind('\n').map_or(0, |index| index + 1);
+        let line = file_contents[line_start..line_end].trim();
+        if line.starts_with("#[") {
+            let mut line_attributes = Vec::new();
+            let mut rest = line;
+            while let Some(attribute) = rest.strip_prefix('#') {
+                let close = match find_closing_delimiter(attribute, 0) {
+                    Some(close) => close,
+                    None => break,
+                };
+                line_attributes.push(attribute[1..close].trim().to_string());
+                rest = attribute[close + 1..].trim_start();
+            }
+            if !rest.is_empty() {
+                break;
+            }
+            // Keep the attributes in the order they're written
+            line_attributes.extend(attributes);
+            attributes = line_attributes;
+        } else if !line.is_empty() && !line.starts_with("//") {
+            break;
+        }
+        if line_start == 0 {
+            break;
+        }
+        line_end = line_start - 1;
+    }
+    attributes
+}
+
+/// Splits an attribute's contents into its name and message. The message is
+/// the string assigned to the attribute, as in `must_use = "reason"`, or the
+/// `note` argument of an attribute such as
+/// `deprecated(since = "1.0", note = "reason")`.
+pub fn parse_attribute(attribute: &str) -> (&str, Option<String>) {
+    let name_end = attribute
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+        .unwrap_or(attribute.len());
+    let (name, rest) = attribute.split_at(name_end);
+    let rest = rest.trim();
+
+    let message = if let Some(value) = rest.strip_prefix('=') {
+        parse_string_literal(value.trim())
+    } else if rest.starts_with('(') {
+        split_top_level(rest, 1, rest.len().saturating_sub(1), b',').iter().find_map(|part| {
+            let (key, value) =
+                rest[part.start_byte as usize..part.end_byte as usize].split_once('=')?;
+            if key.trim() == "note" {
+                parse_string_literal(value.trim())
+            } else {
+                None
+            }
+        })
+    } else {
+        None
+    };
+    (name, message)
+}
+
+/// Returns the contents of a string literal such as `"text"`
+fn parse_string_literal(literal: &str) -> Option<String> {
+    let contents = literal.strip_prefix('"')?.strip_suffix('"')?;
+    Some(contents.replace("\\\"", "\""))
+}
+
+/// Splits the text between `start` and `end` on the occurrences of
+/// `separator` that aren't nested inside of delimiters or generics
+fn split_top_level(file_contents: &str, start: usize, end: usize, separator: u8) -> Vec<ByteSpan> {
+    let bytes = file_contents.as_bytes();
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut part_start = start;
+    let mut index = start;
+    while index < end {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        match bytes[index] {
+            byte if byte == separator && depth == 0 => {
+                parts.push(ByteSpan { start_byte: part_start as u32, end_byte: index as u32 });
+                part_start = index + 1;
+            }
+            b'(' | b'[' | b'{' | b'<' => depth += 1,
+            // Ignore the arrow in `Fn() -> T` types
+            b'>' if bytes[index - 1] == b'-' => {}
+            b')' | b']' | b'}' | b'>' => depth -= 1,
+            _ => {}
+        }
+        index += 1;
+    }
+    parts.push(ByteSpan { start_byte: part_start as u32, end_byte: end as u32 });
+    parts
+}
+
+/// Returns the end offset and text of the word starting at `start`, if any
+fn next_word(bytes: &[u8], start: usize) -> Option<(usize, &str)> {
+    let start = skip_whitespace(bytes, start);
+    let mut end = start;
+    while end < bytes.len() && is_ident_byte(bytes[end]) {
+        end += 1;
+    }
+    if start == end {
+        return None;
+    }
+    std::str::from_utf8(&bytes[start..end]).ok().map(|word| (end, word))
+}
+
+/// Walks backward from `ident_start` over the keywords and visibility
+/// qualifiers that introduce an item and returns the offset of the first one
+fn find_item_start(file_contents: &str, ident_start: usize) -> usize {
+    let bytes = file_contents.as_bytes();
+    let mut start = ident_start;
+    loop {
+        let mut index = start;
+        while index > 0 && bytes[index - 1].is_ascii_whitespace() {
+            index -= 1;
+        }
+        if index == 0 {
+            break;
+        }
+
+        match bytes[index - 1] {
+            // A restricted visibility such as `pub(crate)`
+            b')' => {
+                let open = match bytes[..index - 1].iter().rposition(|b| *b == b'(') {
+                    Some(open) => open,
+                    None => break,
+                };
+                match previous_word(bytes, open) {
+                    Some((word_start, "pub")) => start = word_start,
+                    _ => break,
+                }
+            }
+            // The ABI string of an `extern "C" fn`
+            b'"' => {
+                let open = match bytes[..index - 1].iter().rposition(|b| *b == b'"') {
+                    Some(open) => open,
+                    None => break,
+                };
+                match previous_word(bytes, open) {
+                    Some((word_start, "extern")) => start = word_start,
+                    _ => break,
+                }
+            }
+            _ => match previous_word(bytes, index) {
+                Some((word_start, word)) if ITEM_KEYWORDS.contains(&word) => start = word_start,
+                _ => break,
+            },
+        }
+    }
+    start
+}
+
+/// Walks forward from `ident_end` to the end of the item's body and returns
+/// the offset just past it
+fn find_item_end(file_contents: &str, ident_end: usize) -> Option<usize> {
+    let bytes = file_contents.as_bytes();
+    let mut depth = 0;
+    let mut index = ident_end;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        match bytes[index] {
+            b'(' | b'[' => depth += 1,
+            b')' | b']' => depth -= 1,
+            b';' if depth == 0 => return Some(index + 1),
+            b'{' if depth == 0 => {
+                return find_closing_delimiter(file_contents, index).map(|end| end + 1);
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Given the offset of an opening delimiter, returns the offset of the
+/// matching closing delimiter, ignoring any delimiters inside of comments and
+/// literals
+fn find_closing_delimiter(file_contents: &str, open: usize) -> Option<usize> {
+    let bytes = file_contents.as_bytes();
+    let (open_byte, close_byte) = match bytes.get(open)? {
+        b'(' => (b'(', b')'),
+        b'[' => (b'[', b']'),
+        b'{' => (b'{', b'}'),
+        _ => return None,
+    };
+    let mut depth = 0;
+    let mut index = open;
+    while index < bytes.len() {
+        if let Some(next) = skip_non_code(file_contents, index) {
+            index = next;
+            continue;
+        }
+        if bytes[index] == open_byte {
+            depth += 1;
+        } else if bytes[index] == close_byte {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+        index += 1;
+    }
+    None
+}
+
+/// If a comment, string literal, or character literal starts at `index`,
+/// returns the offset just past it
+fn skip_non_code(file_contents: &str, index: usize) -> Option<usize> {
+    let bytes = file_contents.as_bytes();
+    let rest = &bytes[index..];
+    if rest.starts_with(b"//") {
+        let end = rest.iter().position(|b| *b == b'\n').map_or(bytes.len(), |pos| index + pos);
+        return Some(end);
+    }
+    if rest.starts_with(b"/*") {
+        // Block comments may be nested
+        let mut depth = 0;
+        let mut offset = 0;
+        while offset < rest.len() {
+            if rest[offset..].starts_with(b"/*") {
+                depth += 1;
+                offset += 2;
+            } else if rest[offset..].starts_with(b"*/") {
+                depth -= 1;
+                offset += 2;
+                if depth == 0 {
+                    return Some(index + offset);
+                }
+            } else {
+                offset += 1;
+            }
+        }
+        return Some(bytes.len());
+    }
+
+    // Literals can't start in the middle of an identifier
+    let follows_ident = index > 0 && is_ident_byte(bytes[index - 1]);
+    if !follows_ident {
+        // Raw strings, e.g. r#"..."# and br"..."
+        let raw_start = if rest.starts_with(b"br") {
+            Some(2)
+        } else if rest.starts_with(b"r") {
+            Some(1)
+        } else {
+            None
+        };
+        if let Some(raw_start) = raw_start {
+            let hashes = rest[raw_start..].iter().take_while(|b| **b == b'#').count();
+            if rest.get(raw_start + hashes) == Some(&b'"') {
+                let terminator = format!("\"{}", "#".repeat(hashes)).into_bytes();
+                let body_start = raw_start + hashes + 1;
+                let end = rest[body_start..]
+                    .windows(terminator.len())
+                    .position(|window| window == terminator.as_slice())
+                    .map_or(bytes.len(), |pos| index + body_start + pos + terminator.len());
+                return Some(end);
+            }
+        }
+    }
+
+    let quote_offset = if !follows_ident && rest.starts_with(b"b") { 1 } else { 0 };
+    match rest.get(quote_offset) {
+        Some(b'"') => {
+            let mut offset = quote_offset + 1;
+            while offset < rest.len() {
+                match rest[offset] {
+                    b'\\' => offset += 2,
+                    b'"' => return Some(index + offset + 1),
+                    _ => offset += 1,
+                }
+            }
+            Some(bytes.len())
+        }
+        Some(b'\'') => {
+            let literal_start = index + quote_offset + 1;
+            if bytes.get(literal_start) == Some(&b'\\') {
+                // An escaped character literal. The escaped character itself may be a
+                // quote so the search starts after it.
+                let end = bytes
+                    .get(literal_start + 2..)?
+                    .iter()
+                    .position(|b| *b == b'\'')
+                    .map_or(bytes.len(), |pos| literal_start + 2 + pos + 1);
+                return Some(end);
+            }
+            // Distinguish a character literal from a lifetime by checking for the
+            // closing quote after a single character
+            let next_char = file_contents.get(literal_start..)?.chars().next()?;
+            let closing = literal_start + next_char.len_utf8();
+            if bytes.get(closing) == Some(&b'\'') {
+                Some(closing + 1)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the start offset and text of the identifier that ends at `end`
+fn previous_word(bytes: &[u8], end: usize) -> Option<(usize, &str)> {
+    let mut end = end;
+    while end > 0 && bytes[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    let mut start = end;
+    while start > 0 && is_ident_byte(bytes[start - 1]) {
+        start -= 1;
+    }
+    if start == end {
+        return None;
+    }
+    std::str::from_utf8(&bytes[start..end]).ok().map(|word| (start, word))
+}
+
+/// Walks backward from a binding at `index` over the pattern that contains it,
+/// such as `(mut x, Point { y, .. })`, and returns the offset just past the
+/// `let` keyword that starts the pattern. Returns `None` if the binding isn't
+/// in the pattern of a `let` statement, such as a function parameter.
+fn find_let_keyword(bytes: &[u8], index: usize) -> Option<usize> {
+    let mut index = skip_whitespace_backward(bytes, index);
+    while index > 0 {
+        match bytes[index - 1] {
+            b'(' | b')' | b'[' | b']' | b'{' | b'}' | b',' | b':' | b'.' | b'&' | b'@' => {
+                index -= 1;
+            }
+            byte if is_ident_byte(byte) => {
+                let (word_start, word) = previous_word(bytes, index)?;
+                match word {
+                    "let" => return Some(index),
+                    "box" | "mut" | "ref" => {}
+                    "for" => return None,
+                    _ if EXPRESSION_KEYWORDS.contains(&word) || ITEM_KEYWORDS.contains(&word) => {
+                        return None;
+                    }
+                    _ => {}
+                }
+                index = word_start;
+            }
+            _ => return None,
+        }
+        index = skip_whitespace_backward(bytes, index);
+    }
+    None
+}
+
+/// Moves `index` backward past any ASCII whitespace before it
+fn skip_whitespace_backward(bytes: &[u8], index: usize) -> usize {
+    let mut index = index;
+    while index > 0 && bytes[index - 1].is_ascii_whitespace() {
+        index -= 1;
+    }
+    index
+}
+
+/// Advances `index` past any ASCII whitespace
+fn skip_whitespace(bytes: &[u8], index: usize) -> usize {
+    let mut index = index;
+    while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+        index += 1;
+    }
+    index
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the span of the first occurrence of `ident` in `text`
+    fn ident_span(text: &str, ident: &str) -> ByteSpan {
+        let start_byte = text.find(ident).unwrap() as u32;
+        ByteSpan { start_byte, end_byte: start_byte + ident.len() as u32 }
+    }
+
+    /// Returns the text covered by the span of the item named `ident`
+    fn item_text<'a>(text: &'a str, ident: &str) -> &'a str {
+        let span = find_item_span(text, &ident_span(text, ident)).unwrap();
+        &text[span.start_byte as usize..span.end_byte as usize]
+    }
+
+    #[test]
+    fn parameter_list_works() {
+        let text = "fn add(a: u32, b: (u32, u32)) -> u32 {}";
+        let span = find_parameter_list(text, ident_span(text, "add").end_byte).unwrap();
+        assert_eq!(
+            &text[span.start_byte as usize..=span.end_byte as usize],
+            "(a: u32, b: (u32, u32))"
+        );
+    }
+
+    #[test]
+    fn parameter_list_skips_generics() {
+        let text = "fn apply<F: Fn(u32) -> u32>(f: F) {}";
+        let span = find_parameter_list(text, ident_span(text, "apply").end_byte).unwrap();
+        assert_eq!(&text[span.start_byte as usize..=span.end_byte as usize], "(f: F)");
+    }
+
+    #[test]
+    fn parameter_list_skips_generics_with_comments() {
+        let text = "fn apply<T /* > */, U: Into<&'static str>>(t: T, u: U) {}";
+        let span = find_parameter_list(text, ident_span(text, "apply").end_byte).unwrap();
+        assert_eq!(&text[span.start_byte as usize..=span.end_byte as usize], "(t: T, u: U)");
+    }
+
+    #[test]
+    fn parameters_are_positional() {
+        let text = "fn run(&'a mut self, (a, b): (u32, u32), map: HashMap<u8, u8>, mut c: u32,) {}";
+        let params: Vec<&str> = find_parameters(text, ident_span(text, "run").end_byte)
+            .unwrap()
+            .iter()
+            .map(|span| text[span.start_byte as usize..span.end_byte as usize].trim())
+            .collect();
+        assert_eq!(params, vec!["(a, b): (u32, u32)", "map: HashMap<u8, u8>", "mut c: u32"]);
+    }
+
+    #[test]
+    fn receiver_works() {
+        assert!(is_receiver("self"));
+        assert!(is_receiver(" &mut self"));
+        assert!(is_receiver("mut self: Box<Self>"));
+        assert!(!is_receiver("self_: u32"));
+        assert!(!is_receiver("mutable: u32"));
+    }
+
+    #[test]
+    fn function_item_span_works() {
+        let text = "mod m {\n    pub(crate) unsafe fn run() {\n        let _ = \"}\";\n    }\n}";
+        assert_eq!(
+            item_text(text, "run"),
+            "pub(crate) unsafe fn run() {\n        let _ = \"}\";\n    }"
+        );
+    }
+
+    #[test]
+    fn item_span_skips_comments_and_literals() {
+        let text = "struct S<'a> {\n    // }\n    a: &'a str, /* } */ b: char,\n}\nconst C: char = '}';\nconst Q: char = '\\'';";
+        assert_eq!(
+            item_text(text, "S"),
+            "struct S<'a> {\n    // }\n    a: &'a str, /* } */ b: char,\n}"
+        );
+    }
+
+    #[test]
+    fn item_span_without_body_works() {
+        let text =
+            "trait T {\n    fn required(&self);\n}\nstruct Unit;\nstruct Tuple(u32, [u8; 2]);";
+        assert_eq!(item_text(text, "required"), "fn required(&self);");
+        assert_eq!(item_text(text, "Unit"), "struct Unit;");
+        assert_eq!(item_text(text, "Tuple"), "struct Tuple(u32, [u8; 2]);");
+    }
+
+    #[test]
+    fn item_span_with_raw_string_works() {
+        let text = "pub extern \"C\" fn ffi() {\n    let _ = r#\"{\"#;\n}";
+        assert_eq!(item_text(text, "ffi"), text);
+    }
+
+    #[test]
+    fn item_name_skips_attributes_and_comments() {
+        let text = "#[cfg(Name)]\n/// Name\npub struct Name { r#type: u8 }";
+        let item_span = ByteSpan { start_byte: 0, end_byte: text.len() as u32 };
+        let span = find_item_name(text, &item_span, "Name").unwrap();
+        assert_eq!(span.start_byte as usize, text.find("struct Name").unwrap() + 7);
+        let span = find_item_name(text, &item_span, "type").unwrap();
+        assert_eq!(&text[span.start_byte as usize..span.end_byte as usize], "r#type");
+        assert!(find_item_name(text, &item_span, "Other").is_none());
+    }
+
+    #[test]
+    fn generic_params_work() {
+        let text = "struct S<'a, T: Into<(u8, u16)>, const N: usize = 3, F: Fn(u8) -> u8> {}";
+        let params = find_generic_params(text, ident_span(text, "S").end_byte);
+        let kinds: Vec<(&GenericParamKind, &str)> =
+            params.iter().map(|param| (&param.kind, param.name.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (&GenericParamKind::Lifetime, "a"),
+                (&GenericParamKind::Type, "T"),
+                (&GenericParamKind::Const, "N"),
+                (&GenericParamKind::Type, "F"),
+            ]
+        );
+        let span = &params[2].span;
+        assert_eq!(&text[span.start_byte as usize..span.end_byte as usize], "N");
+    }
+
+    #[test]
+    fn non_generic_item_has_no_params() {
+        let text = "fn run() {}";
+        assert!(find_generic_params(text, ident_span(text, "run").end_byte).is_empty());
+    }
+
+    #[test]
+    fn generic_param_uses_work() {
+        let text = "fn id<T: Clone>(t: T) -> T {\n    // T\n    let _ = x.T;\n    t\n}\nstruct T;";
+        let decl_span = ident_span(text, "T");
+        let uses: Vec<usize> = find_generic_param_uses(text, "T", &decl_span)
+            .iter()
+            .map(|span| span.start_byte as usize)
+            .collect();
+        assert_eq!(uses, vec![text.find("t: T").unwrap() + 3, text.find("-> T").unwrap() + 3]);
+    }
+
+    #[test]
+    fn macro_definitions_work() {
+        let text = "// macro_rules! commented {}\nmacro_rules! first { () => { \"}\" }; }\nmacro_rules! second(() => {});\nfn main() {}";
+        let definitions = find_macro_definitions(text);
+        assert_eq!(definitions.len(), 2);
+        let span_text = |span: &ByteSpan| &text[span.start_byte as usize..span.end_byte as usize];
+        assert_eq!(span_text(&definitions[0].name_span), "first");
+        assert_eq!(span_text(&definitions[0].item_span), "macro_rules! first { () => { \"}\" }; }");
+        assert_eq!(span_text(&definitions[1].item_span), "macro_rules! second(() => {});");
+    }
+
+    #[test]
+    fn macro_name_works() {
+        let text = "let _ = std::println!(\"{}\", x);";
+        let call_start = text.find("std").unwrap() as u32;
+        let name_span = find_macro_name(text, call_start).unwrap();
+        assert_eq!(&text[name_span.start_byte as usize..name_span.end_byte as usize], "println");
+        assert!(find_macro_name(text, 0).is_none());
+    }
+
+    #[test]
+    fn call_span_works() {
+        let text = "let a = parse (x); let b = parse::<Vec<u8>>(x); let c = parse; \
+            let d = parse::X; let e = parse as fn(u8);";
+        let calls: Vec<Option<&str>> = text
+            .match_indices("parse")
+            .map(|(start, name)| {
+                let name_span =
+                    ByteSpan { start_byte: start as u32, end_byte: (start + name.len()) as u32 };
+                find_call_span(text, &name_span)
+                    .map(|span| &text[span.start_byte as usize..span.end_byte as usize])
+            })
+            .collect();
+        assert_eq!(calls, vec![Some("parse (x)"), Some("parse::<Vec<u8>>(x)"), None, None, None]);
+
+        let text = "if self.shapes[0].get()?.area(f(1)) > 0 { <T as Shape>::area(&x) + \
+            Vec::<u8>::new(); \"a.b\".len(); }";
+        let call = |name: &str| {
+            let span = find_call_span(text, &ident_span(text, name)).unwrap();
+            &text[span.start_byte as usize..span.end_byte as usize]
+        };
+        assert_eq!(call("area"), "self.shapes[0].get()?.area(f(1))");
+        assert_eq!(call("new"), "Vec::<u8>::new()");
+        assert_eq!(call("len"), "\"a.b\".len()");
+        let second_area = text.rfind("area").unwrap() as u32;
+        let span =
+            find_call_span(text, &ByteSpan { start_byte: second_area, end_byte: second_area + 4 })
+                .unwrap();
+        assert_eq!(
+            &text[span.start_byte as usize..span.end_byte as usize],
+            "<T as Shape>::area(&x)"
+        );
+    }
+
+    #[test]
+    fn uninitialized_local_works() {
+        let text = "let array: [u8; 4];\nlet mut boxed: Box<dyn Fn() -> u8>;\nlet count = 1;\n\
+            let typed: u8 = 2;\nfn e(param: u8) {}\nlet (left, mut right): (u8, [u8; { 2 }]);\n\
+            let (one, two) = (1, 2);\nlet Point { x: px, .. }: Point;\n\
+            if let Some(inner) = maybe {}\nfor item in items {}";
+        let uninitialized: Vec<bool> = [
+            "array", "boxed", "count", "typed", "param", "left", "right", "one", "px", "inner",
+            "item",
+        ]
+        .iter()
+        .map(|name| is_uninitialized_local(text, &ident_span(text, name)))
+        .collect();
+        assert_eq!(
+            uninitialized,
+            vec![true, true, false, false, false, true, true, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn enum_variants_work() {
+        let text = "enum E<T> where T: Copy {\n    /// Docs, with a comma\n    #[default]\n    A,\n    B(T, u8) = 0x1F,\n    C { c: T } = OFFSET + (1, 2).0,\n    D\n}";
+        let variants = find_enum_variants(text, ident_span(text, "E").end_byte);
+        let variants: Vec<(&str, Option<&str>)> = variants
+            .iter()
+            .map(|variant| {
+                let span = &variant.name_span;
+                (
+                    &text[span.start_byte as usize..span.end_byte as usize],
+                    variant.discriminant.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            variants,
+            vec![("A", None), ("B", Some("0x1F")), ("C", Some("OFFSET + (1, 2).0")), ("D", None)]
+        );
+    }
+
+    #[test]
+    fn integer_literals_work() {
+        assert_eq!(parse_integer_literal("0x1F"), Some(31));
+        assert_eq!(parse_integer_literal("- 3"), Some(-3));
+        assert_eq!(parse_integer_literal("1_000u32"), Some(1000));
+        assert_eq!(parse_integer_literal("0b101i8"), Some(5));
+        assert_eq!(parse_integer_literal("OFFSET + 1"), None);
+    }
+
+    /// Renders the parsed type so that it can be compared in tests
+    fn render_type(syntax: &TypeSyntax) -> String {
+        let render_all = |types: &[TypeSyntax]| {
+            types.iter().map(render_type).collect::<Vec<String>>().join(", ")
+        };
+        match syntax {
+            TypeSyntax::Path { name, args, .. } if args.is_empty() => name.clone(),
+            TypeSyntax::Path { name, args, .. } => format!("{}<{}>", name, render_all(args)),
+            TypeSyntax::Reference(inner) => format!("&{}", render_type(inner)),
+            TypeSyntax::Slice(inner) => format!("[{}]", render_type(inner)),
+            TypeSyntax::Array(inner) => format!("[{}; _]", render_type(inner)),
+            TypeSyntax::Tuple(types) => format!("({})", render_all(types)),
+            TypeSyntax::Other => "?".to_string(),
+        }
+    }
+
+    #[test]
+    fn type_alias_value_works() {
+        let text = "type Res<T = Vec<u8>> = std::result::Result<T, (u8, [&'a mut Error; 4])>;";
+        let span = find_type_alias_value(text, ident_span(text, "Res").end_byte).unwrap();
+        assert_eq!(
+            &text[span.start_byte as usize..span.end_byte as usize],
+            " std::result::Result<T, (u8, [&'a mut Error; 4])>"
+        );
+        assert_eq!(render_type(&parse_type(text, &span)), "Result<T, (u8, [&Error; _])>");
+    }
+
+    #[test]
+    fn parse_type_works() {
+        let parse = |text: &str| {
+            render_type(&parse_type(text, &ByteSpan { start_byte: 0, end_byte: text.len() as u32 }))
+        };
+        assert_eq!(parse("(u8,)"), "(u8)");
+        assert_eq!(parse("(u8)"), "u8");
+        assert_eq!(parse("()"), "()");
+        assert_eq!(parse("&[Cow<'static, str>]"), "&[Cow<str>]");
+        assert_eq!(parse("Box<dyn Fn() -> u8>"), "Box<?>");
+        assert_eq!(parse("fn(u8) -> u8"), "?");
+    }
+
+    #[test]
+    fn item_visibility_works() {
+        let text = "pub const unsafe extern \"system\" fn run() {}";
+        assert_eq!(find_item_visibility(text, &ident_span(text, "run")), Some("pub".to_string()));
+
+        let text = "pub(crate) async fn fetch() {}\nextern fn callback() {}";
+        assert_eq!(
+            find_item_visibility(text, &ident_span(text, "fetch")),
+            Some("pub(crate)".to_string())
+        );
+        assert_eq!(find_item_visibility(text, &ident_span(text, "callback")), None);
+
+        let text =
+            "pub(in crate::a) unsafe fn first() {}\npub(self) fn second() {}\nstruct S(pub u8);";
+        assert_eq!(
+            find_item_visibility(text, &ident_span(text, "first")),
+            Some("pub(in crate::a)".to_string())
+        );
+        assert_eq!(find_item_visibility(text, &ident_span(text, "second")), None);
+        assert_eq!(find_item_visibility(text, &ident_span(text, "u8")), Some("pub".to_string()));
+    }
+
+    #[test]
+    fn impl_keyword_works() {
+        let text =
+            "fn f() {}\nunsafe impl<T: Fn() -> u8, const N: usize> Send for Wrapper<T, N> {}";
+        let keyword = find_impl_keyword(text, ident_span(text, "Wrapper").start_byte).unwrap();
+        assert_eq!(keyword.start_byte, ident_span(text, "impl").start_byte);
+        assert_eq!(&text[keyword.start_byte as usize..keyword.end_byte as usize], "impl");
+        assert_eq!(find_generic_params(text, keyword.end_byte).len(), 2);
+
+        let text = "struct Wrapper;\nfn f() { Wrapper }";
+        assert!(find_impl_keyword(text, text.rfind("Wrapper").unwrap() as u32).is_none());
+    }
+
+    #[test]
+    fn outer_attributes_works() {
+        let text =
+            "fn other() {}\n\n#[test]\n/// Docs\n#[should_panic(expected = \"[x]\")] #[inline]\n\
+            // Comment\n#[ignore] pub fn check() {}";
+        assert_eq!(
+            find_outer_attributes(text, &ident_span(text, "check")),
+            vec!["test", "should_panic(expected = \"[x]\")", "inline", "ignore"]
+        );
+        assert!(find_outer_attributes(text, &ident_span(text, "other")).is_empty());
+    }
+
+    #[test]
+    fn parse_attribute_works() {
+        assert_eq!(parse_attribute("test"), ("test", None));
+        assert_eq!(parse_attribute("tokio::test"), ("tokio::test", None));
+        assert_eq!(
+            parse_attribute("must_use = \"the \\\"result\\\"\""),
+            ("must_use", Some("the \"result\"".to_string()))
+        );
+        assert_eq!(
+            parse_attribute("deprecated(since = \"1.2\", note = \"use `run`, not this\")"),
+            ("deprecated", Some("use `run`, not this".to_string()))
+        );
+        assert_eq!(parse_attribute("deprecated(since = \"1.2\")"), ("deprecated", None));
+    }
+
+    #[test]
+    fn module_declarations_works() {
+        let text = "// mod commented;\nmod first;\n#[path = \"other/path.rs\"]\npub mod second;\n\
+                    mod outer {\n    fn f() { let s = \"mod fake;\"; }\n    mod inner { mod third; }\n\
+                    mod fourth;\n}\nmod fifth;";
+        let declarations = find_module_declarations(text);
+        let found: Vec<(&str, Option<&str>, Vec<&str>)> = declarations
+            .iter()
+            .map(|declaration| {
+                (
+                    declaration.name.as_str(),
+                    declaration.path.as_deref(),
+                    declaration.inline_modules.iter().map(|name| name.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("first", None, vec![]),
+                ("second", Some("other/path.rs"), vec![]),
+                ("third", None, vec!["outer", "inner"]),
+                ("fourth", None, vec!["outer"]),
+                ("fifth", None, vec![]),
+            ]
+        );
+        let span = &declarations[0].name_span;
+        assert_eq!(&text[span.start_byte as usize..span.end_byte as usize], "first");
+    }
+}
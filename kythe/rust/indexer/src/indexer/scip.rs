@@ -0,0 +1,476 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::KytheError;
+use crate::providers::FileProvider;
+use crate::writer::KytheWriter;
+
+use super::analyzers::ByteSpan;
+use super::entries::EntryEmitter;
+use super::offset::OffsetIndex;
+
+use path_clean::clean;
+use scip_rust_proto::{
+    Document, Index, Occurrence, PositionEncoding, SymbolInformation, SymbolRole,
+};
+use std::path::PathBuf;
+use storage_rust_proto::*;
+
+/// The kind of the last descriptor of a SCIP symbol, such as `Method` for
+/// `rust-analyzer cargo shapes 0.1.0 Square#area().`
+#[derive(Debug, PartialEq, Eq)]
+enum DescriptorKind {
+    Namespace,
+    Type,
+    Term,
+    Method,
+    TypeParameter,
+    Parameter,
+    Macro,
+    Meta,
+    /// A symbol that is only unique within its document, such as `local 3`
+    Local,
+}
+
+/// A data structure for converting SCIP indexes, such as the ones written by
+/// `rust-analyzer scip`, into Kythe graph entries
+pub struct ScipConverter<'a> {
+    writer: &'a mut dyn KytheWriter,
+    corpus: String,
+    root: String,
+}
+
+impl<'a> ScipConverter<'a> {
+    /// Create a new instance of the ScipConverter. The files and symbols of
+    /// the converted indexes are placed in `corpus` and `root`.
+    pub fn new(writer: &'a mut dyn KytheWriter, corpus: &str, root: &str) -> Self {
+        Self { writer, corpus: corpus.to_string(), root: root.to_string() }
+    }
+
+    /// Converts a SCIP index and writes its entries
+    ///
+    /// The text of documents that isn't stored in the index is read from
+    /// `provider` using the document's path relative to the project root. The
+    /// digest passed to the provider is empty.
+    pub fn convert(
+        &mut self,
+        index: &Index,
+        provider: &mut dyn FileProvider,
+    ) -> Result<(), KytheError> {
+        let mut generator = EntryGenerator {
+            emitter: EntryEmitter::new(self.writer),
+            corpus: &self.corpus,
+            root: &self.root,
+            offset_index: OffsetIndex::default(),
+        };
+        for document in index.get_documents() {
+            generator.convert_document(document, provider)?;
+        }
+        // External symbols are defined by other indexes, but their documentation
+        // and relationships may only be recorded in this one
+        for symbol in index.get_external_symbols() {
+            generator.emit_symbol_information(symbol, "")?;
+        }
+
+        // We must flush the writer to ensure that all entries get written
+        self.writer.flush()
+    }
+}
+
+/// Returns the path of the directory that the documents of an index are
+/// relative to, if it is a local directory. The project root is stored as a
+/// `file://` URI.
+pub fn get_project_root(index: &Index) -> Option<PathBuf> {
+    let project_root = index.get_metadata().get_project_root();
+    let path = project_root.strip_prefix("file://")?;
+    Some(PathBuf::from(decode_uri_path(path)?))
+}
+
+/// Decodes the percent-encoded bytes in the path of a URI, such as `%20`
+fn decode_uri_path(path: &str) -> Option<String> {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = path.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Emits the entries for the documents and symbols of a SCIP index
+struct EntryGenerator<'a> {
+    emitter: EntryEmitter<'a>,
+    corpus: &'a str,
+    root: &'a str,
+    offset_index: OffsetIndex,
+}
+
+impl<'a> EntryGenerator<'a> {
+    /// Emits the file node of a document, the anchors of its occurrences,
+    /// and the nodes of the symbols it defines
+    fn convert_document(
+        &mut self,
+        document: &Document,
+        provider: &mut dyn FileProvider,
+    ) -> Result<(), KytheError> {
+        let relative_path = document.get_relative_path();
+        // The columns of the occurrences can't be converted to byte offsets without
+        // knowing the code units they are counted in
+        if document.get_position_encoding() == PositionEncoding::UnspecifiedPositionEncoding {
+            return Err(KytheError::IndexerError(format!(
+                "The position encoding of document {} is not specified",
+                relative_path
+            )));
+        }
+        let file_name = clean(relative_path);
+        let file_contents = if document.get_text().is_empty() {
+            let file_bytes = provider.contents(relative_path, "")?;
+            String::from_utf8(file_bytes).map_err(|_| {
+                KytheError::IndexerError(format!(
+                    "Failed to read file {} as UTF8 string",
+                    relative_path
+                ))
+            })?
+        } else {
+            document.get_text().to_string()
+        };
+
+        // https://kythe.io/docs/schema/#file
+        let file_vname = self.file_vname(&file_name);
+        self.emitter.emit_fact(&file_vname, "/kythe/node/kind", b"file".to_vec())?;
+        self.emitter.emit_fact(&file_vname, "/kythe/language", b"rust".to_vec())?;
+        self.emitter.emit_fact(&file_vname, "/kythe/text", file_contents.as_bytes().to_vec())?;
+        self.offset_index.add_file(&file_name, &file_contents);
+
+        let encoding = document.get_position_encoding();
+        for occurrence in document.get_occurrences() {
+            self.emit_occurrence(occurrence, &file_vname, encoding)?;
+        }
+        for symbol in document.get_symbols() {
+            self.emit_symbol_information(symbol, &file_name)?;
+        }
+        Ok(())
+    }
+
+    /// Emits the anchor of an occurrence with an edge to its symbol.
+    /// Occurrences that only highlight syntax don't have a symbol and are
+    /// skipped.
+    fn emit_occurrence(
+        &mut self,
+        occurrence: &Occurrence,
+        file_vname: &VName,
+        encoding: PositionEncoding,
+    ) -> Result<(), KytheError> {
+        let symbol = occurrence.get_symbol();
+        if symbol.is_empty() {
+            return Ok(());
+        }
+        let file_name = file_vname.get_path();
+        let symbol_vname = self.symbol_vname(symbol, file_name);
+        let byte_span = match self.get_byte_span(file_name, occurrence.get_range(), encoding) {
+            Some(byte_span) => byte_span,
+            None => {
+                return self.emitter.emit_diagnostic(
+                    file_vname,
+                    "Failed to convert SCIP occurrence",
+                    Some(&format!(
+                        "The range {:?} of the occurrence of \"{}\" is outside of the file",
+                        occurrence.get_range(),
+                        symbol
+                    )),
+                    None,
+                );
+            }
+        };
+
+        let mut anchor_vname = file_vname.clone();
+        anchor_vname.set_language("rust".to_string());
+        anchor_vname.set_signature(format!("scip_{}_{}", byte_span.start_byte, byte_span.end_byte));
+
+        let roles = occurrence.get_symbol_roles();
+        if has_role(roles, SymbolRole::Definition) {
+            self.emit_symbol_node(&symbol_vname, symbol)?;
+            self.emitter.emit_anchor(
+                &anchor_vname,
+                &symbol_vname,
+                byte_span.start_byte,
+                byte_span.end_byte,
+            )?;
+
+            // Emit a defines anchor over the entire definition so that positions inside
+            // of the definition can be mapped back to it
+            let enclosing_span =
+                self.get_byte_span(file_name, occurrence.get_enclosing_range(), encoding);
+            if let Some(enclosing_span) = enclosing_span {
+                let mut full_anchor_vname = anchor_vname.clone();
+                full_anchor_vname.set_signature(format!(
+                    "scip_{}_{}_defines",
+                    enclosing_span.start_byte, enclosing_span.end_byte
+                ));
+                self.emitter.emit_anchor_edge(
+                    &full_anchor_vname,
+                    &symbol_vname,
+                    enclosing_span,
+                    "/kythe/edge/defines",
+                )?;
+            }
+        } else if has_role(roles, SymbolRole::Import) {
+            self.emitter.emit_anchor_edge(
+                &anchor_vname,
+                &symbol_vname,
+                byte_span,
+                "/kythe/edge/ref/imports",
+            )?;
+        } else if has_role(roles, SymbolRole::WriteAccess) {
+            self.emitter.emit_anchor_edge(
+                &anchor_vname,
+                &symbol_vname,
+                byte_span,
+                "/kythe/edge/ref/writes",
+            )?;
+        } else {
+            self.emitter.emit_reference(&anchor_vname, &symbol_vname, byte_span)?;
+        }
+        Ok(())
+    }
+
+    /// Emits the node kind of a symbol defined in the index, which is based on
+    /// the kind of its last descriptor
+    fn emit_symbol_node(&mut self, symbol_vname: &VName, symbol: &str) -> Result<(), KytheError> {
+        let (kind, subkind) = match get_descriptor_kind(symbol) {
+            DescriptorKind::Namespace => ("record", Some("module")),
+            DescriptorKind::Type => ("record", None),
+            DescriptorKind::Term => ("variable", None),
+            DescriptorKind::Method => ("function", None),
+            DescriptorKind::TypeParameter => ("tvar", None),
+            DescriptorKind::Parameter => ("variable", Some("local/parameter")),
+            DescriptorKind::Macro => ("macro", None),
+            DescriptorKind::Local => ("variable", Some("local")),
+            DescriptorKind::Meta => return Ok(()),
+        };
+        self.emitter.emit_fact(symbol_vname, "/kythe/node/kind", kind.as_bytes().to_vec())?;
+        if let Some(subkind) = subkind {
+            self.emitter.emit_fact(symbol_vname, "/kythe/subkind", subkind.as_bytes().to_vec())?;
+        }
+        Ok(())
+    }
+
+    /// Emits the documentation of a symbol, the edge to the symbol enclosing
+    /// it, and the edges for its relationships to other symbols
+    fn emit_symbol_information(
+        &mut self,
+        information: &SymbolInformation,
+        file_name: &str,
+    ) -> Result<(), KytheError> {
+        let symbol = information.get_symbol();
+        let symbol_vname = self.symbol_vname(symbol, file_name);
+
+        // https://kythe.io/docs/schema/#doc
+        let docs = information.get_documentation().join("\n\n");
+        if !docs.trim().is_empty() {
+            let mut doc_vname = symbol_vname.clone();
+            doc_vname.set_signature(format!("{}_doc", symbol_vname.get_signature()));
+            self.emitter.emit_fact(&doc_vname, "/kythe/node/kind", b"doc".to_vec())?;
+            self.emitter.emit_fact(&doc_vname, "/kythe/text", docs.trim().as_bytes().to_vec())?;
+            self.emitter.emit_edge(&doc_vname, &symbol_vname, "/kythe/edge/documents")?;
+        }
+
+        let enclosing_symbol = information.get_enclosing_symbol();
+        if !enclosing_symbol.is_empty() {
+            let enclosing_vname = self.symbol_vname(enclosing_symbol, file_name);
+            self.emitter.emit_edge(&symbol_vname, &enclosing_vname, "/kythe/edge/childof")?;
+        }
+
+        // References and definitions that are shared with another symbol don't have
+        // an equivalent edge in the Kythe schema
+        for relationship in information.get_relationships() {
+            let target_vname = self.symbol_vname(relationship.get_symbol(), file_name);
+            if relationship.get_is_implementation() {
+                // Methods implement the trait methods they override, and other
+                // symbols, such as types, implement traits
+                let edge_kind = if get_descriptor_kind(symbol) == DescriptorKind::Method {
+                    "/kythe/edge/overrides"
+                } else {
+                    "/kythe/edge/satisfies"
+                };
+                self.emitter.emit_edge(&symbol_vname, &target_vname, edge_kind)?;
+            }
+            if relationship.get_is_type_definition() {
+                self.emitter.emit_edge(&symbol_vname, &target_vname, "/kythe/edge/typed")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn file_vname(&self, file_name: &str) -> VName {
+        let mut vname = VName::new();
+        vname.set_corpus(self.corpus.to_string());
+        vname.set_root(self.root.to_string());
+        vname.set_path(file_name.to_string());
+        vname
+    }
+
+    /// Creates the VName of a symbol, whose signature is the symbol. Local
+    /// symbols are only unique within their document, so their VName also
+    /// contains the document's path.
+    fn symbol_vname(&self, symbol: &str, file_name: &str) -> VName {
+        let mut vname = VName::new();
+        vname.set_corpus(self.corpus.to_string());
+        vname.set_root(self.root.to_string());
+        if get_descriptor_kind(symbol) == DescriptorKind::Local {
+            vname.set_path(file_name.to_string());
+        }
+        vname.set_language("rust".to_string());
+        vname.set_signature(symbol.to_string());
+        vname
+    }
+
+    /// Returns the byte span of a SCIP range in a file. Returns `None` if the
+    /// range is malformed or outside of the file.
+    fn get_byte_span(
+        &self,
+        file_name: &str,
+        range: &[i32],
+        encoding: PositionEncoding,
+    ) -> Option<ByteSpan> {
+        // Ranges on a single line omit the ending line
+        let (line_start, character_start, line_end, character_end) = match *range {
+            [line_start, character_start, line_end, character_end] => {
+                (line_start, character_start, line_end, character_end)
+            }
+            [line, character_start, character_end] => (line, character_start, line, character_end),
+            _ => return None,
+        };
+        Some(ByteSpan {
+            start_byte: self.get_byte_offset(file_name, line_start, character_start, encoding)?,
+            end_byte: self.get_byte_offset(file_name, line_end, character_end, encoding)?,
+        })
+    }
+
+    /// Returns the byte offset of a zero-indexed line and character in a file
+    fn get_byte_offset(
+        &self,
+        file_name: &str,
+        line: i32,
+        character: i32,
+        encoding: PositionEncoding,
+    ) -> Option<u32> {
+        let line = u32::try_from(line).ok()? + 1;
+        let (line_start, line_end) = self.offset_index.get_line_span(file_name, line)?;
+        let file_contents = self.offset_index.get_file_contents(file_name)?;
+        let line_contents = &file_contents[line_start as usize..line_end as usize];
+        let column = get_column_byte(line_contents, usize::try_from(character).ok()?, encoding)?;
+        Some(line_start + column as u32)
+    }
+}
+
+/// Returns whether the roles of an occurrence include `role`
+fn has_role(roles: i32, role: SymbolRole) -> bool {
+    roles & role as i32 != 0
+}
+
+/// Returns the byte offset within a line of a character counted in the code
+/// units of `encoding`. Returns `None` if the character is past the end of the
+/// line or inside of a code point.
+fn get_column_byte(line: &str, character: usize, encoding: PositionEncoding) -> Option<usize> {
+    let mut code_units = 0;
+    for (byte_offset, code_point) in line.char_indices() {
+        if code_units >= character {
+            return (code_units == character).then_some(byte_offset);
+        }
+        code_units += match encoding {
+            PositionEncoding::UTF16CodeUnitOffsetFromLineStart => code_point.len_utf16(),
+            PositionEncoding::UTF32CodeUnitOffsetFromLineStart => 1,
+            PositionEncoding::UTF8CodeUnitOffsetFromLineStart
+            | PositionEncoding::UnspecifiedPositionEncoding => code_point.len_utf8(),
+        };
+    }
+    (code_units == character).then_some(line.len())
+}
+
+/// Returns the kind of the last descriptor of a symbol. Descriptors are
+/// identified by their suffix, such as `/` for namespaces and `().` for
+/// methods.
+fn get_descriptor_kind(symbol: &str) -> DescriptorKind {
+    if symbol.starts_with("local ") {
+        return DescriptorKind::Local;
+    }
+    if symbol.ends_with(").") {
+        return DescriptorKind::Method;
+    }
+    match symbol.chars().last() {
+        Some('/') => DescriptorKind::Namespace,
+        Some('#') => DescriptorKind::Type,
+        Some('.') => DescriptorKind::Term,
+        Some(']') => DescriptorKind::TypeParameter,
+        Some(')') => DescriptorKind::Parameter,
+        Some('!') => DescriptorKind::Macro,
+        _ => DescriptorKind::Meta,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_descriptor_kind_works() {
+        let package = "rust-analyzer cargo shapes 0.1.0";
+        assert_eq!(get_descriptor_kind(&format!("{} shapes/", package)), DescriptorKind::Namespace);
+        assert_eq!(get_descriptor_kind(&format!("{} Square#", package)), DescriptorKind::Type);
+        assert_eq!(get_descriptor_kind(&format!("{} Square#side.", package)), DescriptorKind::Term);
+        assert_eq!(
+            get_descriptor_kind(&format!("{} Square#area().", package)),
+            DescriptorKind::Method
+        );
+        assert_eq!(
+            get_descriptor_kind(&format!("{} Square#[T]", package)),
+            DescriptorKind::TypeParameter
+        );
+        assert_eq!(get_descriptor_kind(&format!("{} vec!", package)), DescriptorKind::Macro);
+        assert_eq!(get_descriptor_kind("local 3"), DescriptorKind::Local);
+    }
+
+    #[test]
+    fn get_column_byte_works() {
+        let line = "let é = \"😀\";";
+        let utf8 = PositionEncoding::UTF8CodeUnitOffsetFromLineStart;
+        let utf16 = PositionEncoding::UTF16CodeUnitOffsetFromLineStart;
+        let utf32 = PositionEncoding::UTF32CodeUnitOffsetFromLineStart;
+        assert_eq!(get_column_byte(line, 6, utf8), Some(6));
+        assert_eq!(get_column_byte(line, 5, utf8), None);
+        assert_eq!(get_column_byte(line, 6, utf16), Some(7));
+        assert_eq!(get_column_byte(line, 11, utf16), Some(14));
+        assert_eq!(get_column_byte(line, 10, utf32), Some(14));
+        assert_eq!(get_column_byte(line, 12, utf32), Some(line.len()));
+        assert_eq!(get_column_byte(line, 13, utf32), None);
+    }
+
+    #[test]
+    fn get_project_root_works() {
+        let mut index = Index::new();
+        index.mut_metadata().set_project_root("file:///home/user/my%20project".to_string());
+        assert_eq!(get_project_root(&index), Some(PathBuf::from("/home/user/my project")));
+
+        index.mut_metadata().set_project_root("https://example.com/project".to_string());
+        assert_eq!(get_project_root(&index), None);
+    }
+}
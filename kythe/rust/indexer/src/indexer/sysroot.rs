@@ -0,0 +1,213 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::KytheError;
+use crate::providers::LocalFileProvider;
+
+use analysis_rust_proto::*;
+use path_clean::clean;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// The crates distributed with the toolchain whose sources are included in the
+/// `rust-src` component
+pub const SYSROOT_CRATES: &[&str] = &["alloc", "core", "proc_macro", "std", "test"];
+
+/// The corpus that the sources of the sysroot crates are indexed in by default
+pub const DEFAULT_SYSROOT_CORPUS: &str = "rust-src";
+
+/// The corpus and root that the sources of the sysroot crates are indexed in
+#[derive(Clone, Debug, Default)]
+pub struct SysrootCorpus {
+    pub corpus: String,
+    pub root: String,
+}
+
+/// Returns whether the crate is one of the toolchain's sysroot crates
+pub fn is_sysroot_crate(name: &str) -> bool {
+    SYSROOT_CRATES.contains(&name)
+}
+
+/// Returns the path of a standard library source file relative to the root of
+/// the `rust-src` component, such as `library/core/src/option.rs` for
+/// `/rustc/<commit hash>/library/core/src/option.rs`. Returns `None` if the
+/// file isn't part of the standard library's sources.
+pub fn get_library_path(file_name: &str) -> Option<&str> {
+    if file_name.starts_with("library/") {
+        return Some(file_name);
+    }
+    let index = file_name.find("/library/")?;
+    Some(&file_name[index + 1..])
+}
+
+/// Finds the save-analysis files of the sysroot crates in the `rust-analysis`
+/// component of the toolchain at `sysroot`. Returns the name of each crate and
+/// the path of its save-analysis file.
+pub fn find_sysroot_analysis_files(sysroot: &Path) -> Result<Vec<(String, PathBuf)>, KytheError> {
+    let mut analysis_files = Vec::new();
+    for target_dir in std::fs::read_dir(sysroot.join("lib").join("rustlib"))? {
+        let analysis_dir = target_dir?.path().join("analysis");
+        if !analysis_dir.is_dir() {
+            continue;
+        }
+        for entry in std::fs::read_dir(analysis_dir)? {
+            let path = entry?.path();
+            // The files are named lib<crate name>-<hash>.json
+            let krate_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .filter(|name| name.ends_with(".json"))
+                .and_then(|name| name.strip_prefix("lib"))
+                .and_then(|name| name.split(['-', '.']).next())
+                .map(String::from);
+            match krate_name {
+                Some(krate_name) if is_sysroot_crate(&krate_name) => {
+                    analysis_files.push((krate_name, path))
+                }
+                _ => {}
+            }
+        }
+    }
+    analysis_files.sort();
+    Ok(analysis_files)
+}
+
+/// Creates a CompilationUnit for indexing the sysroot crate whose save-analysis
+/// is at `analysis_path`. The crate's source files are read from the `rust-src`
+/// component of the toolchain at `sysroot` and registered with `provider`, and
+/// are given VNames in `sysroot_corpus`.
+pub fn create_sysroot_unit(
+    sysroot: &Path,
+    analysis_path: &Path,
+    sysroot_corpus: &SysrootCorpus,
+    provider: &mut LocalFileProvider,
+) -> Result<CompilationUnit, KytheError> {
+    let analysis_contents = std::fs::read_to_string(analysis_path)?;
+    let analysis = rls_analysis::deserialize_crate_data(&analysis_contents).ok_or_else(|| {
+        KytheError::IndexerError(format!(
+            "Failed to deserialize save-analysis file {}",
+            analysis_path.display()
+        ))
+    })?;
+
+    // The source files referenced by the save-analysis use the paths that the
+    // standard library was built with
+    let file_names: BTreeSet<String> = analysis
+        .defs
+        .iter()
+        .map(|def| &def.span)
+        .chain(analysis.refs.iter().map(|reference| &reference.span))
+        .filter_map(|span| span.file_name.to_str())
+        .map(clean)
+        .collect();
+
+    let mut unit = CompilationUnit::new();
+    let rust_src = sysroot.join("lib").join("rustlib").join("src").join("rust");
+    for file_name in file_names.iter() {
+        let library_path = match get_library_path(file_name) {
+            Some(library_path) => library_path,
+            None => continue,
+        };
+        let local_path = rust_src.join(library_path);
+        if !local_path.is_file() {
+            continue;
+        }
+        let contents = std::fs::read(&local_path)?;
+        unit.mut_required_input().push(create_file_input(
+            file_name,
+            library_path,
+            &contents,
+            sysroot_corpus,
+        ));
+        unit.mut_source_file().push(file_name.clone());
+        provider.add_file(file_name, &local_path);
+    }
+    if unit.get_source_file().is_empty() {
+        return Err(KytheError::IndexerError(format!(
+            "Failed to find the sources for {} in {}",
+            analysis_path.display(),
+            rust_src.display()
+        )));
+    }
+
+    // Add the save-analysis so that it can be found by the indexer
+    let analysis_file_name = analysis_path.to_string_lossy().to_string();
+    let analysis_vname_path = analysis_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    unit.mut_required_input().push(create_file_input(
+        &analysis_file_name,
+        &analysis_vname_path,
+        analysis_contents.as_bytes(),
+        sysroot_corpus,
+    ));
+    provider.add_file(&analysis_file_name, analysis_path);
+
+    let mut unit_vname = VName::new();
+    unit_vname.set_corpus(sysroot_corpus.corpus.clone());
+    unit_vname.set_root(sysroot_corpus.root.clone());
+    unit_vname.set_language("rust".to_string());
+    unit.set_v_name(unit_vname);
+    Ok(unit)
+}
+
+/// Creates a required input for the file named `file_name` with the VName
+/// path `vname_path` in the sysroot corpus
+fn create_file_input(
+    file_name: &str,
+    vname_path: &str,
+    contents: &[u8],
+    sysroot_corpus: &SysrootCorpus,
+) -> CompilationUnit_FileInput {
+    let mut vname = VName::new();
+    vname.set_corpus(sysroot_corpus.corpus.clone());
+    vname.set_root(sysroot_corpus.root.clone());
+    vname.set_path(vname_path.to_string());
+
+    let mut file_info = FileInfo::new();
+    file_info.set_path(file_name.to_string());
+    file_info.set_digest(hex::encode(Sha256::digest(contents)));
+
+    let mut file_input = CompilationUnit_FileInput::new();
+    file_input.set_v_name(vname);
+    file_input.set_info(file_info);
+    file_input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_library_path_works() {
+        assert_eq!(
+            get_library_path("/rustc/a1b2c3/library/core/src/option.rs"),
+            Some("library/core/src/option.rs")
+        );
+        assert_eq!(
+            get_library_path("library/alloc/src/vec/mod.rs"),
+            Some("library/alloc/src/vec/mod.rs")
+        );
+        assert_eq!(get_library_path("src/main.rs"), None);
+    }
+
+    #[test]
+    fn is_sysroot_crate_works() {
+        assert!(is_sysroot_crate("core"));
+        assert!(is_sysroot_crate("std"));
+        assert!(!is_sysroot_crate("hashbrown"));
+    }
+}
@@ -16,9 +16,10 @@ use crate::error::KytheError;
 use crate::proxyrequests;
 use analysis_rust_proto::*;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, BufReader, Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use zip::ZipArchive;
 
 /// A trait for retrieving files during indexing.
@@ -223,3 +224,40 @@ impl FileProvider for ProxyFileProvider {
         Ok(content)
     }
 }
+
+/// A [FileProvider] that reads files from the local file system. Each file
+/// must be registered with the location it should be read from.
+#[derive(Default)]
+pub struct LocalFileProvider {
+    /// A map between a file's path in the CompilationUnit and its location on
+    /// the local file system
+    files: HashMap<String, PathBuf>,
+}
+
+impl LocalFileProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the file at `local_path` to be returned for requests to
+    /// `path`
+    pub fn add_file(&mut self, path: &str, local_path: &Path) {
+        self.files.insert(path.to_string(), local_path.to_path_buf());
+    }
+}
+
+impl FileProvider for LocalFileProvider {
+    /// Given a file path, returns whether the file has been registered and
+    /// exists on the file system. The digest is ignored.
+    fn exists(&mut self, path: &str, _digest: &str) -> Result<bool, KytheError> {
+        Ok(matches!(self.files.get(path), Some(local_path) if local_path.is_file()))
+    }
+
+    /// Given a file path, returns the vector of bytes of the file from the file
+    /// system. The digest is ignored.
+    fn contents(&mut self, path: &str, _digest: &str) -> Result<Vec<u8>, KytheError> {
+        let local_path =
+            self.files.get(path).ok_or_else(|| KytheError::FileNotFoundError(path.to_string()))?;
+        Ok(std::fs::read(local_path)?)
+    }
+}
@@ -0,0 +1,67 @@
+// Verifies that attributes and qualifiers are recorded as tags
+
+//- @old_api defines/binding OldApi
+//- OldApi.tag/deprecated "Use new_api instead"
+#[deprecated(since = "1.0.0", note = "Use new_api instead")]
+pub fn old_api() {}
+
+//- @older_api defines/binding OlderApi
+//- OlderApi.tag/deprecated ""
+#[deprecated]
+pub fn older_api() {}
+
+//- @oldest_api defines/binding OldestApi
+//- OldestApi.tag/deprecated "Use new_api instead"
+#[deprecated(
+    since = "0.1.0",
+    note = "Use new_api instead"
+)]
+pub fn oldest_api() {}
+
+macro_rules! must_use_fn {
+    ($name:ident) => {
+        #[must_use = "The result is the only output"]
+        pub fn $name() -> u32 {
+            0
+        }
+    };
+}
+
+//- @generated defines/binding Generated
+//- Generated.tag/must_use "The result is the only output"
+must_use_fn!(generated);
+
+//- @Handle defines/binding Handle
+//- Handle.tag/must_use "Must be closed after use"
+//- !{ Handle.tag/deprecated _ }
+#[must_use = "Must be closed after use"]
+pub struct Handle;
+
+//- @read_raw defines/binding ReadRaw
+//- ReadRaw.tag/unsafe ""
+//- !{ ReadRaw.tag/const _ }
+pub unsafe fn read_raw() {}
+
+//- @fetch defines/binding Fetch
+//- Fetch.tag/async ""
+pub async fn fetch() {}
+
+//- @square defines/binding Square
+//- Square.tag/const ""
+pub const fn square(value: u32) -> u32 {
+    value * value
+}
+
+//- @callback defines/binding Callback
+//- Callback.tag/abi "C"
+pub extern "C" fn callback() {}
+
+//- @system_callback defines/binding SystemCallback
+//- SystemCallback.tag/abi "system"
+//- SystemCallback.tag/unsafe ""
+pub unsafe extern "system" fn system_callback() {}
+
+//- @main defines/binding Main
+//- !{ Main.tag/test _ }
+//- !{ Main.tag/unsafe _ }
+fn main() {}
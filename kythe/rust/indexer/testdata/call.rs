@@ -0,0 +1,52 @@
+// Verifies that calls to functions and methods are emitted as ref/call anchors
+// over the whole call expression
+
+//- @callee defines/binding FnCallee
+fn callee() -> u32 {
+    1
+}
+
+struct _Counter {
+    count: u32,
+}
+
+impl _Counter {
+    //- @increment defines/binding FnIncrement
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+//- @caller defines/binding FnCaller
+fn caller() {
+    //- CalleeCall=@"callee()" ref/call FnCallee
+    //- CalleeCall childof FnCaller
+    //- CalleeName=@callee ref FnCallee
+    //- !{ CalleeName ref/call _ }
+    let total = callee();
+
+    let mut counter = _Counter { count: total };
+    //- IncrementCall=@"counter.increment()" ref/call FnIncrement
+    //- IncrementCall childof FnCaller
+    counter.increment();
+
+    //- PathCall=@"_Counter::increment(&mut counter)" ref/call FnIncrement
+    //- PathCall childof FnCaller
+    _Counter::increment(&mut counter);
+
+    //- PointerRef=@callee ref FnCallee
+    //- !{ PointerRef ref/call _ }
+    let _pointer = callee;
+
+    //- CastRef=@callee ref FnCallee
+    //- !{ CastRef ref/call _ }
+    let _cast = callee as fn() -> u32;
+}
+
+//- @main defines/binding FnMain
+fn main() {
+    //- CallerCall=@"caller()" ref/call FnCaller
+    //- CallerCall childof FnMain
+    //- !{ CallerCall childof FnCaller }
+    caller();
+}
@@ -1,6 +1,7 @@
 // Verifies that documentation nodes are emitted for indexed elements
 
-//- @+2"main" defines/binding FnMain
+//- @+3"main" defines/binding FnMain
+//- @"/// The main function" documents FnMain
 /// The main function
 fn main() {
     println!("Hello, world!");
@@ -9,3 +10,39 @@ fn main() {
 //- FnMain.node/kind function
 //- FnDoc.text "The main function"
 //- FnDoc documents FnMain
+
+//- SquareDoc documents Square
+//- SquareDoc.text "A square whose [`Self::area`] is computed by [its method]"
+//- SquareDoc param.0 Area
+//- SquareDoc param.1 Area
+//- SquareDoc ref/doc Area
+/// A square whose [`Self::area`] is computed by [its method](Square::area)
+//- @Square defines/binding Square
+struct Square {
+    side: u32,
+}
+
+impl Square {
+    //- AreaDoc documents Area
+    //- AreaDoc.text "Returns the area. See [`Square`] and [`main`]."
+    //- AreaDoc param.0 Square
+    //- AreaDoc param.1 FnMain
+    //- AreaDoc ref/doc Square
+    //- AreaDoc ref/doc FnMain
+    /// Returns the area. See [`Square`] and [`main`].
+    //- @area defines/binding Area
+    fn area(&self) -> u32 {
+        self.side * self.side
+    }
+
+    //- PerimeterDocAnchor documents Perimeter
+    //- PerimeterDocAnchor.loc/start @^"/// Returns the perimeter"
+    //- PerimeterDocAnchor.loc/end @$"/// of the square"
+    /// Returns the perimeter
+    /// of the square
+    #[inline]
+    //- @_perimeter defines/binding Perimeter
+    fn _perimeter(&self) -> u32 {
+        4 * self.side
+    }
+}
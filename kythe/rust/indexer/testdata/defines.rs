@@ -0,0 +1,63 @@
+// Verifies that defines anchors are emitted over the full span of definitions
+
+//- @_empty defines/binding EmptyFn
+//- EmptyFnDefines defines EmptyFn
+//- EmptyFnDefines.loc/start @^"pub(crate)"
+//- EmptyFnDefines.loc/end @$"}"
+pub(crate) fn _empty() {}
+
+//- @_Point defines/binding Point
+//- PointDefines defines Point
+//- PointDefines.loc/start @^struct
+//- PointDefines.loc/end @$"}"
+struct _Point {
+    x: u32,
+}
+
+//- @_Unit defines/binding Unit
+//- UnitDefines defines Unit
+//- UnitDefines.loc/start @^struct
+//- UnitDefines.loc/end @$";"
+struct _Unit;
+
+//- @_Direction defines/binding Direction
+//- DirectionDefines defines Direction
+//- DirectionDefines.loc/start @^enum
+//- DirectionDefines.loc/end @$"}"
+enum _Direction {
+    Up,
+}
+
+//- @Shape defines/binding Shape
+//- ShapeDefines defines Shape
+//- ShapeDefines.loc/start @^trait
+trait Shape {
+    //- @area defines/binding AreaFn
+    //- AreaFnDefines defines AreaFn
+    //- AreaFnDefines.loc/start @^fn
+    //- AreaFnDefines.loc/end @$";"
+    fn area(&self) -> u32;
+}
+
+//- @inner defines/binding InnerMod
+//- InnerModDefines defines InnerMod
+//- InnerModDefines.loc/start @^mod
+mod inner {
+    //- @_nested defines/binding NestedFn
+    //- NestedFnDefines defines NestedFn
+    //- NestedFnDefines.loc/start @^pub
+    //- NestedFnDefines.loc/end @$"}"
+    pub fn _nested() {
+        let _text = "{";
+    }
+}
+
+impl Shape for _Point {
+    //- @area defines/binding PointAreaFn
+    //- PointAreaFnDefines defines PointAreaFn
+    //- PointAreaFnDefines.loc/start @^fn
+    //- PointAreaFnDefines.loc/end @$"}"
+    fn area(&self) -> u32 { self.x }
+}
+
+fn main() {}
@@ -23,11 +23,42 @@ enum _ConstantEnum {
     //- @Constant1 defines/binding EnumConstant1
     //- EnumConstant1 childof ConstantEnum
     //- EnumConstant1.node/kind constant
+    //- EnumConstant1.text "0"
     Constant1,
     //- @Constant2 defines/binding EnumConstant2
     //- EnumConstant2 childof ConstantEnum
     //- EnumConstant2.node/kind constant
+    //- EnumConstant2.text "5"
     Constant2 = 5,
+    //- @Constant3 defines/binding EnumConstant3
+    //- EnumConstant3.text "6"
+    Constant3,
+}
+
+const _BASE: isize = 10;
+
+//- @_ReprEnum defines/binding ReprEnum
+//- ReprEnum.tag/repr "u8"
+#[repr(u8)]
+enum _ReprEnum {
+    //- @Hex defines/binding HexConstant
+    //- HexConstant childof ReprEnum
+    //- HexConstant.text "31"
+    Hex = 0x1F,
+    //- @AfterHex defines/binding AfterHexConstant
+    //- AfterHexConstant.text "32"
+    AfterHex,
+}
+
+//- @_ExpressionEnum defines/binding ExpressionEnum
+//- !{ ExpressionEnum.tag/repr _ }
+enum _ExpressionEnum {
+    //- @Base defines/binding BaseConstant
+    //- BaseConstant.text "_BASE"
+    Base = _BASE,
+    //- @AfterBase defines/binding AfterBaseConstant
+    //- AfterBaseConstant.text "_BASE + 1"
+    AfterBase,
 }
 
 //- @_StructEnum defines/binding StructEnum
@@ -0,0 +1,37 @@
+// Verifies that items declared in extern blocks are handled properly
+#![feature(extern_types)]
+
+//- ImplicitModAnchor defines/implicit ImplicitMod
+//- ImplicitModAnchor.loc/start 0
+
+extern "C" {
+    //- @abs defines/binding FnAbs
+    //- FnAbs.node/kind function
+    //- FnAbs.complete incomplete
+    //- FnAbs childof ImplicitMod
+    fn abs(input: i32) -> i32;
+
+    //- @errno defines/binding StaticErrno
+    //- StaticErrno.node/kind variable
+    //- StaticErrno.complete incomplete
+    //- StaticErrno childof ImplicitMod
+    //- StaticErrno typed I32Type
+    //- I32Type.node/kind tbuiltin
+    static errno: i32;
+}
+
+//- @ffi defines/binding ModFfi
+mod ffi {
+    extern "C" {
+        //- @_Opaque defines/binding TypeOpaque
+        //- TypeOpaque.node/kind record
+        //- TypeOpaque.complete incomplete
+        //- TypeOpaque childof ModFfi
+        //- !{ TypeOpaque childof ImplicitMod }
+        pub type _Opaque;
+    }
+}
+
+fn main() {
+    let _value = unsafe { abs(errno) };
+}
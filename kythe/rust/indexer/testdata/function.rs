@@ -1,4 +1,4 @@
-// Verifies that function nodes are emitted and well-formed
+// Verifies that function nodes and their parameters are emitted and well-formed
 
 //- @main defines/binding FnMain
 //- FnMain.node/kind function
@@ -6,3 +6,50 @@
 fn main() {
     println!("Hello, world!");
 }
+
+//- @_add defines/binding FnAdd
+//- FnAdd param.0 ArgLhs
+//- FnAdd param.1 ArgRhs
+//- ArgLhs childof FnAdd
+//- ArgRhs childof FnAdd
+//- !{ FnAdd param.2 _ }
+//- @lhs defines/binding ArgLhs
+//- @rhs defines/binding ArgRhs
+fn _add(lhs: u32, rhs: u32) -> u32 {
+    //- @sum defines/binding LocalSum
+    //- !{ LocalSum childof FnAdd }
+    let sum = lhs + rhs;
+    sum
+}
+
+struct _Counter {
+    count: u32,
+}
+
+impl _Counter {
+    //- @_increment defines/binding FnIncrement
+    //- FnIncrement param.0 ArgAmount
+    //- @amount defines/binding ArgAmount
+    fn _increment(&mut self, amount: u32) {
+        self.count += amount;
+    }
+}
+
+//- @_scale defines/binding FnScale
+//- FnScale param.1 ArgFactor
+//- !{ FnScale param.0 _ }
+//- ArgFirst childof FnScale
+//- ArgSecond childof FnScale
+//- @first defines/binding ArgFirst
+//- @second defines/binding ArgSecond
+//- @factor defines/binding ArgFactor
+fn _scale((first, second): (u32, u32), factor: u32) -> (u32, u32) {
+    (first * factor, second * factor)
+}
+
+//- @_last defines/binding FnLast
+//- FnLast param.0 ArgItems
+//- @items defines/binding ArgItems
+fn _last<T /* > */: Copy>(items: &[T]) -> Option<T> {
+    items.last().copied()
+}
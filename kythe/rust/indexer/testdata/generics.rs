@@ -0,0 +1,81 @@
+// Verifies that generic parameters are emitted as tvar nodes
+
+//- @_Wrapper defines/binding Wrapper
+//- Wrapper tparam.0 WrapperElem
+//- Wrapper tparam.1 WrapperLen
+//- !{ Wrapper tparam.2 _ }
+//- @TElem defines/binding WrapperElem
+//- WrapperElem.node/kind tvar
+//- @NLen defines/binding WrapperLen
+//- WrapperLen.node/kind tvar
+struct _Wrapper<'a, TElem, const NLen: usize> {
+    //- @TElem ref WrapperElem
+    //- @NLen ref WrapperLen
+    items: &'a [TElem; NLen],
+}
+
+//- @_Either defines/binding Either
+//- Either tparam.0 EitherLeft
+//- Either tparam.1 EitherRight
+//- @TLeft defines/binding EitherLeft
+//- @TRight defines/binding EitherRight
+enum _Either<TLeft, TRight> {
+    //- @TLeft ref EitherLeft
+    _Left(TLeft),
+    //- @TRight ref EitherRight
+    _Right(TRight),
+}
+
+//- @_Convert defines/binding Convert
+//- Convert tparam.0 ConvertTarget
+//- @TTarget defines/binding ConvertTarget
+//- ConvertTarget.node/kind tvar
+trait _Convert<TTarget> {
+    //- @TTarget ref ConvertTarget
+    fn _convert(&self) -> TTarget;
+}
+
+//- @_identity defines/binding Identity
+//- Identity tparam.0 IdentityValue
+//- @#0TValue defines/binding IdentityValue
+//- @#1TValue ref IdentityValue
+//- @#2TValue ref IdentityValue
+fn _identity<TValue: Clone>(value: TValue) -> TValue {
+    value
+}
+
+//- ConvertImpl.node/kind record
+//- ConvertImpl.subkind impl
+//- ConvertImpl tparam.0 ImplInner
+//- !{ ConvertImpl tparam.1 _ }
+//- @#0TInner defines/binding ImplInner
+//- ImplInner.node/kind tvar
+//- @#1TInner ref ImplInner
+impl<TInner> _Convert<u32> for _Either<TInner, u32> {
+    fn _convert(&self) -> u32 {
+        0
+    }
+}
+
+//- WrapperImpl.subkind impl
+//- WrapperImpl tparam.0 WrapperImplElem
+//- WrapperImpl tparam.1 WrapperImplLen
+//- @#0TImplElem defines/binding WrapperImplElem
+//- @#1TImplElem ref WrapperImplElem
+//- @#0NImplLen defines/binding WrapperImplLen
+//- WrapperImplLen.node/kind tvar
+//- @#1NImplLen ref WrapperImplLen
+impl<'a, TImplElem, const NImplLen: usize> _Wrapper<'a, TImplElem, NImplLen> {
+    //- @#2NImplLen ref WrapperImplLen
+    fn _len(&self) -> usize {
+        NImplLen
+    }
+}
+
+//- @_Pair defines/binding Pair
+//- Pair.node/kind talias
+//- Pair tparam.0 PairElem
+//- @TPairElem defines/binding PairElem
+type _Pair<TPairElem> = (TPairElem, TPairElem);
+
+fn main() {}
@@ -0,0 +1,31 @@
+// Verifies that imports are emitted as ref/imports anchors
+
+//- @core ref/imports CoreCrate
+//- CoreCrate.node/kind package
+extern crate core;
+
+//- @shapes defines/binding ShapesMod
+mod shapes {
+    //- @area defines/binding AreaFn
+    pub fn area(width: u32, height: u32) -> u32 {
+        width * height
+    }
+}
+
+//- @colors defines/binding ColorsMod
+mod colors {
+    pub const RED: u32 = 0xff0000;
+}
+
+//- @shapes ref ShapesMod
+//- @area ref/imports AreaFn
+//- !{ @area ref AreaFn }
+use shapes::area;
+
+//- @colors ref ColorsMod
+//- @"*" ref/imports ColorsMod
+use colors::*;
+
+fn main() {
+    let _ = area(RED, 2);
+}
@@ -0,0 +1,15 @@
+// Verifies that macro definitions and invocations are handled properly
+
+//- @add_one defines/binding MacroAddOne
+//- MacroAddOne.node/kind macro
+//- DefinesAnchor defines MacroAddOne
+//- DefinesAnchor.loc/start @^"macro_rules"
+//- DefinesAnchor.loc/end @$"}; }"
+macro_rules! add_one { ($value:expr) => { $value + 1 }; }
+
+fn main() {
+    //- @add_one ref/expands MacroAddOne
+    let total = add_one!(1);
+    //- @println ref/expands _PrintlnMacro
+    println!("{}", total);
+}
@@ -0,0 +1,75 @@
+// Verifies that MarkedSource is emitted for definitions
+
+//- @_add defines/binding FnAdd
+//- FnAdd code AddRoot
+//- AddRoot child.0 AddKeyword
+//- AddKeyword.pre_text "fn "
+//- AddRoot child.1 AddName
+//- AddName child.0 AddContext
+//- AddContext.kind "CONTEXT"
+//- AddContext child.0 AddCrate
+//- AddCrate.kind "IDENTIFIER"
+//- AddName child.1 AddIdent
+//- AddIdent.kind "IDENTIFIER"
+//- AddIdent.pre_text "_add"
+//- AddRoot child.2 AddParams
+//- AddParams.kind "PARAMETER"
+//- AddParams child.0 AddLhs
+//- AddLhs.pre_text "lhs: u32"
+//- AddParams child.1 AddRhs
+//- AddRhs.pre_text "rhs: u32"
+//- AddRoot child.4 AddReturn
+//- AddReturn.kind "TYPE"
+//- AddReturn.pre_text "u32"
+fn _add(lhs: u32, rhs: u32) -> u32 {
+    lhs + rhs
+}
+
+//- @_Point defines/binding StructPoint
+//- StructPoint code PointRoot
+//- PointRoot child.0 PointKeyword
+//- PointKeyword.pre_text "struct "
+//- PointRoot child.1 PointName
+//- PointName child.1 PointIdent
+//- PointIdent.pre_text "_Point"
+struct _Point {
+    //- @x_coord defines/binding FieldX
+    //- FieldX code XRoot
+    //- XRoot child.0 XName
+    //- XName child.0 XContext
+    //- XContext child.1 XParent
+    //- XParent.pre_text "_Point"
+    //- XName child.1 XIdent
+    //- XIdent.pre_text "x_coord"
+    //- XRoot child.2 XType
+    //- XType.kind "TYPE"
+    //- XType.pre_text "i64"
+    x_coord: i64,
+}
+
+impl _Point {
+    //- @_origin defines/binding FnOrigin
+    //- FnOrigin code OriginRoot
+    //- OriginRoot child.1 OriginName
+    //- OriginName child.0 OriginContext
+    //- OriginContext child.1 OriginParent
+    //- OriginParent.pre_text "_Point"
+    //- OriginRoot child.3 _OriginArrow
+    //- OriginRoot child.4 OriginReturn
+    //- OriginReturn.pre_text "Self"
+    fn _origin() -> Self {
+        Self { x_coord: 0 }
+    }
+}
+
+fn main() {
+    //- @local_total defines/binding LocalTotal
+    //- LocalTotal code TotalRoot
+    //- TotalRoot child.0 TotalName
+    //- TotalName child.0 TotalIdent
+    //- TotalIdent.pre_text "local_total"
+    //- TotalRoot child.2 TotalType
+    //- TotalType.pre_text "u32"
+    let local_total: u32 = 1;
+    println!("{}", local_total);
+}
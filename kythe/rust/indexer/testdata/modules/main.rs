@@ -1,6 +1,9 @@
 // Verifies that implicit modules have an anchor at the top of the file and
 // exlicit modules have an anchor over their name
 
+//- @test ref TestMod
+//- @test ref/file TestFile
+//- TestFile.node/kind file
 mod test;
 
 //- ImplicitModAnchor.node/kind anchor
@@ -0,0 +1,25 @@
+// Verifies that edges to the sysroot crates aren't emitted when the standard
+// library is disabled
+
+#[allow(dead_code)]
+//- @Point defines/binding Point
+//- !{ Point satisfies _ }
+struct Point {
+    x: u32,
+}
+
+impl Clone for Point {
+    //- @clone defines/binding CloneMethod
+    //- !{ CloneMethod overrides _ }
+    fn clone(&self) -> Self {
+        Point { x: self.x }
+    }
+}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x
+    }
+}
+
+fn main() {}
@@ -1,5 +1,7 @@
 #[path = "../relative_module/mod.rs"]
 //- @relative_module ref RelativeMod
+//- @relative_module ref/file RelativeFile
+//- RelativeFile.node/kind file
 mod relative_module;
 
 fn main() {
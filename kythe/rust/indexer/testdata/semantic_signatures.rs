@@ -0,0 +1,25 @@
+// Verifies that definitions are given signatures based on their canonical
+// paths with --semantic_signatures, including in crates with generic
+// parameters and inherent implementations
+
+//- @Wrapper defines/binding Wrapper=vname("test_crate#type::Wrapper", _, _, _, "rust")
+//- @T defines/binding TypeParam
+//- TypeParam.node/kind tvar
+pub struct Wrapper<T> {
+    //- @value defines/binding Field=vname("test_crate#value::Wrapper::value", _, _, _, "rust")
+    //- Field childof Wrapper
+    value: T,
+}
+
+impl<T: Copy> Wrapper<T> {
+    //- @get defines/binding Get=vname("test_crate#value::{impl#0}::get", _, _, _, "rust")
+    //- Get childof Wrapper
+    pub fn get(&self) -> T {
+        self.value
+    }
+}
+
+fn main() {
+    //- @get ref Get
+    let _ = Wrapper { value: 1u32 }.get();
+}
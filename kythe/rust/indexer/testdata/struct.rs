@@ -11,8 +11,18 @@ struct _TestStruct {
     //- StructField.complete definition
     //- StructField.subkind field
     test_field: String,
+    //- @builtin_field defines/binding BuiltinField
+    //- BuiltinField typed U32Builtin
+    //- U32Builtin=vname("u32#builtin",_,_,_,"rust").node/kind tbuiltin
+    builtin_field: u32,
+    //- @struct_field defines/binding NestedStructField
+    //- NestedStructField typed OtherStruct
+    struct_field: _OtherStruct,
 }
 
+//- @_OtherStruct defines/binding OtherStruct
+struct _OtherStruct {}
+
 impl _TestStruct {
     //- @_test defines/binding TestFn
     //- TestFn.node/kind function
@@ -0,0 +1,14 @@
+// Verifies that references to the sysroot crates target the sysroot corpus,
+// which defaults to "rust-src"
+
+//- @Vec ref VecStruct
+//- VecStruct=vname(_, "rust-src", "", _, "rust")
+fn _count(values: Vec<u32>) -> usize {
+    values.len()
+}
+
+fn main() {
+    //- @println ref/expands PrintlnMacro
+    //- PrintlnMacro=vname(_, "rust-src", "", "library/std/src/macros.rs", "rust")
+    println!("{}", _count(Vec::new()));
+}
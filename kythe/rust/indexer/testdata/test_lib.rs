@@ -4,9 +4,10 @@ fn main() {
     println!("Hello, world!");
 }
 
-//- @+4"test_fn" defines/binding TestFn
+//- @+5"test_fn" defines/binding TestFn
 //- TestFn.node/kind function
 //- TestFn.complete definition
+//- TestFn.tag/test ""
 #[test]
 fn test_fn() {
     //- @x defines/binding VarX
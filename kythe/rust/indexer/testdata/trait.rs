@@ -1,4 +1,5 @@
-// Verifies that traits are properly handled by the indexer
+// Verifies that traits and their implementations are properly handled by the
+// indexer
 
 //- @TestTrait defines/binding Trait
 //- Trait.node/kind interface
@@ -8,14 +9,40 @@ trait TestTrait {
     fn test_method() {}
 }
 
+//- @SubTrait defines/binding SubTrait
+//- SubTrait extends Trait
+//- !{ Trait extends SubTrait }
+trait SubTrait: TestTrait {}
+
 #[allow(dead_code)]
 //- @TestStruct defines/binding Struct
+//- Struct satisfies Trait
+//- Struct satisfies SubTrait
 struct TestStruct {}
 
+impl SubTrait for TestStruct {}
+
 impl TestTrait for TestStruct {
     //- @test_method defines/binding ImplMethod
     //- ImplMethod childof Struct
+    //- ImplMethod overrides TestMethod
+    fn test_method() {}
+}
+
+#[allow(dead_code)]
+impl TestStruct {
+    //- @test_method defines/binding InherentMethod
+    //- !{ InherentMethod overrides _ }
     fn test_method() {}
 }
 
+impl std::fmt::Display for TestStruct {
+    //- @fmt defines/binding FmtMethod
+    //- FmtMethod childof Struct
+    //- FmtMethod overrides _
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TestStruct")
+    }
+}
+
 fn main() {}
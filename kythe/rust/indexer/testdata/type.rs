@@ -6,4 +6,47 @@
 //- TestType aliases U32Type
 type _TestType = u32;
 
+//- @Point defines/binding Point
+struct Point {
+    _x: u32,
+}
+
+//- @Shape defines/binding Shape
+enum Shape {
+    _Circle,
+}
+
+//- @Pair defines/binding Pair
+struct Pair<A, B> {
+    _first: A,
+    _second: B,
+}
+
+//- @_PointAlias defines/binding PointAlias
+//- PointAlias.node/kind talias
+//- PointAlias aliases Point
+type _PointAlias = Point;
+
+//- @_ShapeAlias defines/binding ShapeAlias
+//- ShapeAlias aliases Shape
+type _ShapeAlias = Shape;
+
+//- @_PairAlias defines/binding PairAlias
+//- PairAlias aliases PairApp
+//- PairApp.node/kind tapp
+//- PairApp param.0 Pair
+//- PairApp param.1 Point
+//- PairApp param.2 Shape
+type _PairAlias = Pair<Point, Shape>;
+
+//- @_SliceAlias defines/binding SliceAlias
+//- SliceAlias aliases RefApp
+//- RefApp.node/kind tapp
+//- RefApp param.0 vname("reference#builtin",_,_,_,"rust")
+//- RefApp param.1 SliceApp
+//- SliceApp.node/kind tapp
+//- SliceApp param.0 vname("slice#builtin",_,_,_,"rust")
+//- SliceApp param.1 Point
+type _SliceAlias = &'static [Point];
+
 fn main() {}
@@ -2,24 +2,57 @@
 
 //- @_TEST_CONSTANT defines/binding Constant
 //- Constant.node/kind constant
+//- Constant typed U32Builtin
+//- U32Builtin=vname("u32#builtin",_,_,_,"rust").node/kind tbuiltin
 const _TEST_CONSTANT: u32 = 0;
 
 //- @_TEST_STATIC defines/binding Static
 //- Static.node/kind constant
 static _TEST_STATIC: &str = "Kythe";
 
+//- @_TEST_STRUCT_STATIC defines/binding StructStatic
+//- StructStatic typed TestStruct
+static _TEST_STRUCT_STATIC: TestStruct = TestStruct {};
+
+//- @TestStruct defines/binding TestStruct
+struct TestStruct {}
+
 fn main() {
     //- @_test_variable defines/binding Local
     //- Local.node/kind variable
     //- Local.subkind local
+    //- Local typed I32Builtin
+    //- I32Builtin=vname("i32#builtin",_,_,_,"rust").node/kind tbuiltin
     let _test_variable = 0;
 
-    // TODO: Identify declaractions and emit an ".complete = incomplete" fact
+    //- @_test_struct_variable defines/binding StructLocal
+    //- StructLocal typed TestStruct
+    let _test_struct_variable = TestStruct {};
+
     //- @_test_declaration defines/binding Decl
     //- Decl.node/kind variable
     //- Decl.subkind local
+    //- Decl.complete incomplete
     let _test_declaration: u32;
 
+    //- @_test_array_declaration defines/binding ArrayDecl
+    //- ArrayDecl.complete incomplete
+    let _test_array_declaration: [u8; 4];
+
+    //- @_test_initialized defines/binding Initialized
+    //- !{ Initialized.complete incomplete }
+    let _test_initialized: u32 = 1;
+
+    //- @_test_first defines/binding FirstDecl
+    //- FirstDecl.complete incomplete
+    //- @_test_second defines/binding SecondDecl
+    //- SecondDecl.complete incomplete
+    let (_test_first, mut _test_second): (u32, [u8; 2]);
+
+    //- @_test_left defines/binding Left
+    //- !{ Left.complete incomplete }
+    let (_test_left, _test_right) = (1, 2);
+
     //- @_test_closure defines/binding Closure
     //- Closure.node/kind function
     //- @#0closure_variable defines/binding ClosureVariable
@@ -0,0 +1,65 @@
+// Verifies that the visibility of definitions is recorded
+
+//- @Account defines/binding Account
+//- Account.visibility "pub"
+pub struct Account {
+    //- @id defines/binding Id
+    //- Id.visibility "pub"
+    pub id: u32,
+    //- @balance defines/binding Balance
+    //- Balance.visibility "pub(crate)"
+    pub(crate) balance: u64,
+    //- @secret defines/binding Secret
+    //- Secret.visibility "private"
+    secret: String,
+}
+
+impl Account {
+    //- @open defines/binding Open
+    //- Open.visibility "pub"
+    pub fn open() -> Self {
+        Self { id: 0, balance: 0, secret: String::new() }
+    }
+
+    //- @audit defines/binding Audit
+    //- Audit.visibility "private"
+    fn audit(&self) -> bool {
+        self.secret.is_empty()
+    }
+}
+
+//- @Ledger defines/binding Ledger
+//- Ledger.visibility "pub(crate)"
+pub(crate) trait Ledger {
+    //- @record defines/binding Record
+    //- !{ Record.visibility _ }
+    fn record(&self);
+}
+
+//- @LIMIT defines/binding Limit
+//- Limit.visibility "private"
+const LIMIT: u32 = 10;
+
+//- @accounts defines/binding Accounts
+//- Accounts.visibility "pub"
+pub mod accounts {
+    //- @open_account defines/binding OpenAccount
+    //- OpenAccount.visibility "pub(super)"
+    pub(super) fn open_account() -> u32 {
+        super::LIMIT
+    }
+}
+
+//- @State defines/binding State
+//- State.visibility "private"
+enum State {
+    //- @Active defines/binding Active
+    //- !{ Active.visibility _ }
+    Active,
+}
+
+fn main() {
+    let account = Account::open();
+    let _ = (account.id, account.balance, account.audit(), accounts::open_account());
+    let _ = State::Active;
+}
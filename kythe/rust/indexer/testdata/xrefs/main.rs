@@ -12,7 +12,7 @@
 mod log;
 
 //- @log ref LogMod
-//- @hello_world ref HelloWorldFn
+//- @hello_world ref/imports HelloWorldFn
 use log::hello_world;
 
 //- !{ @std defines/binding _ }
@@ -130,7 +130,7 @@ fn main() {
 
 //- @crate_import defines/binding CrateImportMod
 mod crate_import {
-    //- @crate_import_test ref CrateImportTestFn
+    //- @crate_import_test ref/imports CrateImportTestFn
     use crate::crate_import_test;
 
     //- @run_import_test defines/binding RCIT_Fn
@@ -16,6 +16,7 @@ use crate::array_writer::ArrayWriter;
 
 extern crate kythe_rust_indexer;
 use kythe_rust_indexer::error::KytheError;
+use kythe_rust_indexer::indexer::analyzers::ByteSpan;
 use kythe_rust_indexer::indexer::entries::EntryEmitter;
 use sha2::{Digest, Sha256};
 use storage_rust_proto::*;
@@ -124,6 +125,56 @@ fn anchors_properly_emitted() -> Result<(), KytheError> {
     Ok(())
 }
 
+// This test checks that the emit_anchor_edge function works properly
+#[test]
+fn anchor_edges_properly_emitted() -> Result<(), KytheError> {
+    // Create an emitter that writes to the ArrayWriter. This makes it easier to
+    // look at the emitted Entry protobufs.
+    let mut array_writer = ArrayWriter::new();
+    let mut emitter = EntryEmitter::new(&mut array_writer);
+
+    // Create anchor and target VNames for the test
+    let mut anchor_vname = VName::new();
+    anchor_vname.set_signature("test_signature_anchor".to_string());
+    let mut target_vname = VName::new();
+    target_vname.set_signature("test_signature_target".to_string());
+
+    emitter.emit_anchor_edge(
+        &anchor_vname,
+        &target_vname,
+        ByteSpan { start_byte: 2, end_byte: 10 },
+        "/kythe/edge/defines",
+    )?;
+
+    // There should be 4 Entry protobufs
+    let entries = array_writer.as_ref();
+    assert_eq!(entries.len(), 4);
+
+    // The first Entry defines the anchor
+    let entry0 = entries.get(0).unwrap();
+    assert_eq!(*entry0.get_source(), anchor_vname);
+    assert_eq!(entry0.get_fact_name(), "/kythe/node/kind");
+    assert_eq!(entry0.get_fact_value(), b"anchor".to_vec());
+
+    // The second and third define the start/end of the anchor
+    let entry1 = entries.get(1).unwrap();
+    assert_eq!(entry1.get_fact_name(), "/kythe/loc/start");
+    assert_eq!(entry1.get_fact_value(), b"2".to_vec());
+
+    let entry2 = entries.get(2).unwrap();
+    assert_eq!(entry2.get_fact_name(), "/kythe/loc/end");
+    assert_eq!(entry2.get_fact_value(), b"10".to_vec());
+
+    // The last Entry is the requested edge between the anchor and the target
+    let entry3 = entries.get(3).unwrap();
+    assert_eq!(*entry3.get_source(), anchor_vname);
+    assert_eq!(*entry3.get_target(), target_vname);
+    assert_eq!(entry3.get_fact_name(), "/");
+    assert_eq!(entry3.get_edge_kind(), "/kythe/edge/defines");
+
+    Ok(())
+}
+
 // This test checks that the emit_diagnostic function works properly
 #[test]
 fn diagnostics_properly_emitted() -> Result<(), KytheError> {
@@ -19,3 +19,4 @@ extern crate storage_rust_proto;
 pub mod array_writer;
 mod indexer_entries;
 mod providers;
+mod scip_converter;
@@ -15,7 +15,7 @@
 extern crate kythe_rust_indexer;
 use kythe_rust_indexer::{
     error::KytheError,
-    providers::{FileProvider, KzipFileProvider},
+    providers::{FileProvider, KzipFileProvider, LocalFileProvider},
 };
 extern crate runfiles;
 use runfiles::Runfiles;
@@ -71,3 +71,27 @@ fn test_kzip_provider() {
     let units = kzip_provider.get_compilation_units().unwrap();
     assert_eq!(units.len(), 0, "Expected units vector to be empty");
 }
+
+#[test]
+fn test_local_provider() {
+    let kzip_path = get_runfile("testkzip.kzip");
+    let mut local_provider = LocalFileProvider::new();
+    local_provider.add_file("/rustc/library/test.kzip", &kzip_path);
+
+    // Check the `exists` function
+    assert!(
+        local_provider.exists("/rustc/library/test.kzip", "").unwrap(),
+        "File should exist but doesn't"
+    );
+    assert!(!local_provider.exists("invalid", "").unwrap(), "File shouldn't exist but does");
+
+    // Check the `contents` function
+    let contents = local_provider.contents("/rustc/library/test.kzip", "").unwrap();
+    assert_eq!(contents, std::fs::read(&kzip_path).unwrap(), "File contents did not match");
+    match local_provider.contents("invalid", "") {
+        Err(KytheError::FileNotFoundError(_)) => {}
+        result => {
+            panic!("Unexpected result while getting contents of nonexistent file: {:?}", result)
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2026 The Kythe Authors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::array_writer::ArrayWriter;
+extern crate kythe_rust_indexer;
+use kythe_rust_indexer::error::KytheError;
+use kythe_rust_indexer::indexer::scip::ScipConverter;
+use kythe_rust_indexer::providers::LocalFileProvider;
+use scip_rust_proto::*;
+use storage_rust_proto::*;
+
+const SQUARE: &str = "rust-analyzer cargo shapes 0.1.0 Square#";
+const SHAPE: &str = "rust-analyzer cargo shapes 0.1.0 Shape#";
+
+fn create_occurrence(range: &[i32], symbol: &str, symbol_roles: i32) -> Occurrence {
+    let mut occurrence = Occurrence::new();
+    occurrence.set_range(range.to_vec());
+    occurrence.set_symbol(symbol.to_string());
+    occurrence.set_symbol_roles(symbol_roles);
+    occurrence
+}
+
+fn find_edge<'a>(entries: &'a [Entry], edge_kind: &str) -> Option<&'a Entry> {
+    entries.iter().find(|entry| entry.get_edge_kind() == edge_kind)
+}
+
+// This test checks that occurrences are converted to anchors and relationships
+// to edges
+#[test]
+fn scip_index_converted() -> Result<(), KytheError> {
+    let mut document = Document::new();
+    document.set_relative_path("src/lib.rs".to_string());
+    document.set_text("struct Square;\nimpl Shape for Square {}\n".to_string());
+    document.set_position_encoding(PositionEncoding::UTF8CodeUnitOffsetFromLineStart);
+    document.mut_occurrences().push(create_occurrence(
+        &[0, 7, 13],
+        SQUARE,
+        SymbolRole::Definition as i32,
+    ));
+    document.mut_occurrences().push(create_occurrence(&[1, 15, 1, 21], SQUARE, 0));
+
+    let mut relationship = Relationship::new();
+    relationship.set_symbol(SHAPE.to_string());
+    relationship.set_is_implementation(true);
+    let mut information = SymbolInformation::new();
+    information.set_symbol(SQUARE.to_string());
+    information.mut_documentation().push("A square".to_string());
+    information.mut_relationships().push(relationship);
+    document.mut_symbols().push(information);
+
+    let mut index = Index::new();
+    index.mut_documents().push(document);
+
+    let mut array_writer = ArrayWriter::new();
+    let mut provider = LocalFileProvider::new();
+    ScipConverter::new(&mut array_writer, "test_corpus", "").convert(&index, &mut provider)?;
+    let entries = array_writer.as_ref();
+
+    // The definition is a binding anchor over the name of the struct
+    let binding = find_edge(entries, "/kythe/edge/defines/binding").unwrap();
+    assert_eq!(binding.get_source().get_path(), "src/lib.rs");
+    assert_eq!(binding.get_source().get_signature(), "scip_7_13");
+    assert_eq!(binding.get_target().get_signature(), SQUARE);
+    assert_eq!(binding.get_target().get_corpus(), "test_corpus");
+
+    // The reference's range is converted to the byte offsets of the second line
+    let reference = find_edge(entries, "/kythe/edge/ref").unwrap();
+    assert_eq!(reference.get_source().get_signature(), "scip_30_36");
+
+    let satisfies = find_edge(entries, "/kythe/edge/satisfies").unwrap();
+    assert_eq!(satisfies.get_source().get_signature(), SQUARE);
+    assert_eq!(satisfies.get_target().get_signature(), SHAPE);
+
+    let documents = find_edge(entries, "/kythe/edge/documents").unwrap();
+    assert_eq!(documents.get_source().get_signature(), format!("{}_doc", SQUARE));
+
+    Ok(())
+}
+
+// This test checks that documents without a position encoding are rejected,
+// since their occurrences can't be converted to byte offsets
+#[test]
+fn unspecified_position_encoding_rejected() {
+    let mut document = Document::new();
+    document.set_relative_path("src/lib.rs".to_string());
+    document.set_text("struct Square;\n".to_string());
+    document.mut_occurrences().push(create_occurrence(
+        &[0, 7, 13],
+        SQUARE,
+        SymbolRole::Definition as i32,
+    ));
+
+    let mut index = Index::new();
+    index.mut_documents().push(document);
+
+    let mut array_writer = ArrayWriter::new();
+    let mut provider = LocalFileProvider::new();
+    let result =
+        ScipConverter::new(&mut array_writer, "test_corpus", "").convert(&index, &mut provider);
+    assert!(result.is_err());
+}
@@ -83,14 +83,17 @@ def _rust_extract_impl(ctx):
 
     # Generate the kzip
     output = ctx.outputs.kzip
+    extractor_args = [
+        "--extra_action=%s" % extra_action_file.path,
+        "--output=%s" % output.path,
+        "--vnames_config=%s" % ctx.file._vnames_config_file.path,
+    ]
+    if ctx.attr.def_paths:
+        extractor_args.append("--def_paths")
     ctx.actions.run(
         mnemonic = "RustExtract",
         executable = ctx.executable._extractor,
-        arguments = [
-            "--extra_action=%s" % extra_action_file.path,
-            "--output=%s" % output.path,
-            "--vnames_config=%s" % ctx.file._vnames_config_file.path,
-        ],
+        arguments = extractor_args,
         inputs = [extra_action_file, ctx.file._vnames_config_file] + rustc_lib + rust_std + ctx.files.srcs + all_out_dir_files,
         outputs = [output],
         env = {
@@ -122,6 +125,10 @@ rust_extract = rule(
         "is_test_lib": attr.bool(
             mandatory = True,
         ),
+        # Whether to record the canonical paths of the crate's definitions
+        "def_paths": attr.bool(
+            default = False,
+        ),
         "crate_name": attr.string(
             default = "test_crate",
         ),
@@ -170,6 +177,7 @@ def _rust_entries_impl(ctx):
     # if ctx.attr.metadata_suffix:
     #     iargs += ["-meta", ctx.attr.metadata_suffix]
 
+    iargs += ctx.attr.indexer_args
     iargs += [kzip.path, "| gzip >" + output.path]
 
     cmds = ["set -e", "set -o pipefail", " ".join(iargs), ""]
@@ -192,6 +200,9 @@ rust_entries = rule(
         # Whether to enable anchor scope edges.
         "emit_anchor_scopes": attr.bool(default = False),
 
+        # Additional arguments to pass to the Rust indexer
+        "indexer_args": attr.string_list(default = []),
+
         # The kzip to pass to the Rust indexer
         "kzip": attr.label(
             providers = ["kzip"],
@@ -214,19 +225,25 @@ def _rust_indexer(
         out_dir_files = [],
         is_test_lib = False,
         has_marked_source = False,
-        emit_anchor_scopes = False):
+        emit_anchor_scopes = False,
+        semantic_signatures = False,
+        indexer_args = []):
     kzip = name + "_units"
     rust_extract(
         name = kzip,
         srcs = srcs,
         out_dir_files = out_dir_files,
         is_test_lib = is_test_lib,
+        def_paths = semantic_signatures,
     )
+    if semantic_signatures:
+        indexer_args = indexer_args + ["--semantic_signatures"]
     entries = name + "_entries"
     rust_entries(
         name = entries,
         has_marked_source = has_marked_source,
         emit_anchor_scopes = emit_anchor_scopes,
+        indexer_args = indexer_args,
         kzip = ":" + kzip,
     )
     return entries
@@ -242,7 +259,9 @@ def rust_indexer_test(
         log_entries = False,
         has_marked_source = False,
         emit_anchor_scopes = False,
-        allow_duplicates = False):
+        allow_duplicates = False,
+        semantic_signatures = False,
+        indexer_args = []):
     """
     Runs a Rust verifier test on the source files
 
@@ -257,6 +276,9 @@ def rust_indexer_test(
       has_marked_source: Enable to make the indexer emit Marked Source (unused)
       emit_anchor_scopes: Enable to make the indexer emit anchor scopes (unused)
       allow_duplicates: Enable to make the verifier ignore duplicate entries
+      semantic_signatures: Enable to generate signatures from the canonical
+        paths of definitions
+      indexer_args: Additional arguments to pass to the Rust indexer
     """
 
     # Generate entries using the Rust indexer
@@ -267,6 +289,8 @@ def rust_indexer_test(
         is_test_lib = is_test_lib,
         has_marked_source = has_marked_source,
         emit_anchor_scopes = emit_anchor_scopes,
+        semantic_signatures = semantic_signatures,
+        indexer_args = indexer_args,
     )
 
     opts = ["--use_file_nodes", "--show_goals", "--check_for_singletons"]